@@ -0,0 +1,290 @@
+use std::path::Path;
+
+use anyhow::Context;
+
+/// The known answers for one input, stored next to it as `<user>.output`: the
+/// part one answer on the first line and part two on the second, with an empty
+/// line for a part that hasn't been solved yet.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Expected {
+    parts: [Option<String>; 2],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl Expected {
+    /// A missing file just means nothing is known yet.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Expected::default());
+        }
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format! {"could not read {}", path.display()})?;
+        Ok(Expected::parse(&contents))
+    }
+
+    pub fn parse(contents: &str) -> Self {
+        let mut lines = contents.lines().map(str::trim);
+        let mut next = || lines.next().filter(|l| !l.is_empty()).map(String::from);
+        Expected {
+            parts: [next(), next()],
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let [one, two] = &self.parts;
+        let contents = format! {
+            "{}\n{}\n",
+            one.as_deref().unwrap_or_default(),
+            two.as_deref().unwrap_or_default()
+        };
+        std::fs::write(path, contents)
+            .with_context(|| format! {"could not write {}", path.display()})
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        self.parts[part as usize - 1].as_deref()
+    }
+
+    pub fn check(&self, part: u8, answer: &anyhow::Result<String>) -> Check {
+        match (self.get(part), answer) {
+            (None, _) => Check::Unknown,
+            (Some(expected), Ok(answer)) if expected == answer => Check::Pass,
+            (Some(expected), _) => Check::Fail {
+                expected: expected.to_string(),
+            },
+        }
+    }
+
+    /// Fills in a part that had no known answer. An answer that disagrees with
+    /// the one already on file is refused rather than overwritten.
+    pub fn record(&mut self, part: u8, answer: &str) -> anyhow::Result<bool> {
+        let slot = &mut self.parts[part as usize - 1];
+        match slot {
+            Some(known) if known == answer => Ok(false),
+            Some(known) => {
+                anyhow::bail! {"part {part} is already recorded as {known}, not {answer}"}
+            }
+            None => {
+                *slot = Some(answer.to_string());
+                Ok(true)
+            }
+        }
+    }
+}
+
+/// What the site said about a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl Verdict {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Right => "right",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+        }
+    }
+}
+
+impl std::str::FromStr for Verdict {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        Ok(match s {
+            "right" => Verdict::Right,
+            "wrong" => Verdict::Wrong,
+            "too-high" => Verdict::TooHigh,
+            "too-low" => Verdict::TooLow,
+            _ => anyhow::bail! {"unknown verdict {s:?}"},
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub part: u8,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+/// Every answer submitted for one input, stored next to it as `<user>.log`
+/// with one `<part> <verdict> <answer>` line per submission.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Log {
+    submissions: Vec<Submission>,
+}
+
+impl Log {
+    /// A missing file just means nothing has been submitted yet.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Log::default());
+        }
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format! {"could not read {}", path.display()})?;
+        Log::parse(&contents).with_context(|| format! {"could not parse {}", path.display()})
+    }
+
+    fn parse(contents: &str) -> anyhow::Result<Self> {
+        let mut submissions = Vec::new();
+        for (n, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let mut fields = line.trim().splitn(3, ' ');
+            let (Some(part), Some(verdict), Some(answer)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                anyhow::bail! {"line {}: expected `<part> <verdict> <answer>`", n + 1};
+            };
+            submissions.push(Submission {
+                part: match part {
+                    "1" => 1,
+                    "2" => 2,
+                    _ => anyhow::bail! {"line {}: there is no part {part}", n + 1},
+                },
+                verdict: verdict
+                    .parse()
+                    .with_context(|| format! {"line {}", n + 1})?,
+                answer: answer.to_string(),
+            });
+        }
+        Ok(Log { submissions })
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let contents = self
+            .submissions
+            .iter()
+            .map(|s| format! {"{} {} {}\n", s.part, s.verdict.as_str(), s.answer})
+            .collect::<String>();
+        std::fs::write(path, contents)
+            .with_context(|| format! {"could not write {}", path.display()})
+    }
+
+    /// What the site already said about this exact answer, if it was submitted.
+    pub fn verdict(&self, part: u8, answer: &str) -> Option<Verdict> {
+        self.submissions
+            .iter()
+            .find(|s| s.part == part && s.answer == answer)
+            .map(|s| s.verdict)
+    }
+
+    /// Why the log proves `answer` wrong, if it does: either it was rejected
+    /// before, or it is no better than a number the site called too high or
+    /// too low.
+    pub fn disproves(&self, part: u8, answer: &str) -> Option<String> {
+        let number = answer.parse::<i128>().ok();
+        self.submissions
+            .iter()
+            .filter(|s| s.part == part && s.verdict != Verdict::Right)
+            .find_map(|s| {
+                if s.answer == answer {
+                    return Some(
+                        format! {"{answer} was already rejected ({})", s.verdict.as_str()},
+                    );
+                }
+                let bound = s.answer.parse::<i128>().ok()?;
+                match s.verdict {
+                    Verdict::TooHigh if number? >= bound => {
+                        Some(format! {"{answer} is at least {bound}, which is too high"})
+                    }
+                    Verdict::TooLow if number? <= bound => {
+                        Some(format! {"{answer} is at most {bound}, which is too low"})
+                    }
+                    _ => None,
+                }
+            })
+    }
+
+    pub fn add(&mut self, part: u8, verdict: Verdict, answer: &str) {
+        self.submissions.push(Submission {
+            part,
+            verdict,
+            answer: answer.to_string(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_treats_blank_lines_as_unknown() {
+        let expected = Expected::parse("\n5782\n");
+        assert_eq! {expected.get(1), None};
+        assert_eq! {expected.get(2), Some("5782")};
+        assert_eq! {Expected::parse(""), Expected::default()};
+    }
+
+    #[test]
+    fn it_checks_answers() {
+        let expected = Expected::parse("962\n");
+        assert_eq! {expected.check(1, &Ok("962".to_string())), Check::Pass};
+        assert_eq! {
+            expected.check(1, &Ok("961".to_string())),
+            Check::Fail { expected: "962".to_string() }
+        };
+        assert_eq! {
+            expected.check(1, &Err(anyhow::anyhow! {"Not Implemented yet"})),
+            Check::Fail { expected: "962".to_string() }
+        };
+        assert_eq! {expected.check(2, &Ok("5782".to_string())), Check::Unknown};
+    }
+
+    #[test]
+    fn it_only_records_new_answers() -> anyhow::Result<()> {
+        let mut expected = Expected::parse("962\n");
+        assert! {!expected.record(1, "962")?};
+        assert! {expected.record(1, "961").is_err()};
+        assert! {expected.record(2, "5782")?};
+        assert_eq! {expected, Expected::parse("962\n5782\n")};
+        Ok(())
+    }
+
+    #[test]
+    fn it_round_trips_the_log() -> anyhow::Result<()> {
+        let log = Log::parse("1 too-high 1000\n1 wrong abc def\n\n1 right 962\n")?;
+        assert_eq! {log.verdict(1, "1000"), Some(Verdict::TooHigh)};
+        assert_eq! {log.verdict(1, "abc def"), Some(Verdict::Wrong)};
+        assert_eq! {log.verdict(2, "962"), None};
+
+        let mut added = Log::default();
+        added.add(1, Verdict::TooHigh, "1000");
+        added.add(1, Verdict::Wrong, "abc def");
+        added.add(1, Verdict::Right, "962");
+        assert_eq! {added, log};
+
+        assert! {Log::parse("3 right 1\n").is_err()};
+        assert! {Log::parse("1 close 1\n").is_err()};
+        assert! {Log::parse("1 right\n").is_err()};
+        Ok(())
+    }
+
+    #[test]
+    fn it_uses_rejections_as_bounds() -> anyhow::Result<()> {
+        let log = Log::parse("1 too-high 1000\n1 too-low 10\n1 wrong 500\n2 right 7\n")?;
+        assert! {log.disproves(1, "1000").unwrap().contains("already rejected (too-high)")};
+        assert! {log.disproves(1, "1001").unwrap().contains("too high")};
+        assert! {log.disproves(1, "-3").unwrap().contains("too low")};
+        assert! {log.disproves(1, "500").unwrap().contains("already rejected (wrong)")};
+        assert_eq! {log.disproves(1, "501"), None};
+        assert_eq! {log.disproves(1, "abc"), None};
+        assert_eq! {log.disproves(2, "7"), None};
+        assert_eq! {log.disproves(2, "2000"), None};
+        Ok(())
+    }
+}
@@ -0,0 +1,404 @@
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+
+use crate::answers::{Expected, Log, Verdict};
+use crate::http::{Client, Method, Request, Response};
+
+pub const YEAR: u16 = 2025;
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+/// The session cookie comes from `AOC_SESSION` if set, otherwise from
+/// `cookie_file` (`.cookie` by default).
+pub fn session(cookie_file: &Path) -> anyhow::Result<String> {
+    session_from(std::env::var("AOC_SESSION").ok(), cookie_file)
+}
+
+fn session_from(env: Option<String>, cookie_file: &Path) -> anyhow::Result<String> {
+    if let Some(session) = env.filter(|s| !s.trim().is_empty()) {
+        return Ok(session.trim().to_string());
+    }
+    match std::fs::read_to_string(cookie_file) {
+        Ok(session) if !session.trim().is_empty() => Ok(session.trim().to_string()),
+        Ok(_) => {
+            anyhow::bail! {"{} is empty; paste your session cookie into it", cookie_file.display()}
+        }
+        Err(_) => anyhow::bail! {
+            "no session cookie: save it to {} or set AOC_SESSION",
+            cookie_file.display()
+        },
+    }
+}
+
+pub struct Site {
+    client: Box<dyn Client>,
+    base_url: String,
+    session: String,
+    /// The least time to leave between requests, to go easy on the site.
+    interval: Duration,
+    last_request: Option<Instant>,
+}
+
+impl Site {
+    pub fn new(
+        client: Box<dyn Client>,
+        base_url: &str,
+        session: String,
+        interval: Duration,
+    ) -> Self {
+        Site {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            interval,
+            last_request: None,
+        }
+    }
+
+    fn send(
+        &mut self,
+        method: Method,
+        path: &str,
+        form: Vec<(String, String)>,
+    ) -> anyhow::Result<Response> {
+        if let Some(last) = self.last_request {
+            std::thread::sleep(self.interval.saturating_sub(last.elapsed()));
+        }
+        let request = Request {
+            method,
+            url: format! {"{}/{YEAR}{path}", self.base_url},
+            session: self.session.clone(),
+            form,
+        };
+        let response = self.client.send(&request);
+        self.last_request = Some(Instant::now());
+        let response = response?;
+
+        match response.status {
+            200 if !response.body.contains("Please log in") => Ok(response),
+            200 | 400 | 401 | 403 => anyhow::bail! {
+                "the session cookie was rejected, it has probably expired; log in again and update it"
+            },
+            404 => anyhow::bail! {"{} isn't unlocked yet", request.url},
+            500..=599 => anyhow::bail! {
+                "{} had a server error ({}); try again later",
+                request.url,
+                response.status
+            },
+            status => anyhow::bail! {"{} returned {status}: {}", request.url, response.body.trim()},
+        }
+    }
+
+    pub fn input(&mut self, day: usize) -> anyhow::Result<String> {
+        Ok(self
+            .send(Method::Get, &format! {"/day/{day}/input"}, Vec::new())?
+            .body)
+    }
+
+    pub fn answer(&mut self, day: usize, part: u8, answer: &str) -> anyhow::Result<Outcome> {
+        let form = vec![
+            ("level".to_string(), part.to_string()),
+            ("answer".to_string(), answer.to_string()),
+        ];
+        let response = self.send(Method::Post, &format! {"/day/{day}/answer"}, form)?;
+        parse_outcome(&response.body)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Judged(Verdict),
+    /// Nothing was sent: the `.output` file or the log already has a verdict.
+    Known(Verdict),
+    /// Nothing was sent: the log's verdicts on other answers rule this one
+    /// out, for the reason given.
+    Disproved(String),
+    /// Answered too recently; holds how long the site says is left.
+    TooSoon(String),
+    /// The part is still locked or was already solved on the site.
+    WrongLevel,
+}
+
+fn parse_outcome(body: &str) -> anyhow::Result<Outcome> {
+    let outcome = if body.contains("That's the right answer") {
+        Outcome::Judged(Verdict::Right)
+    } else if body.contains("That's not the right answer") {
+        Outcome::Judged(if body.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if body.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        })
+    } else if body.contains("You gave an answer too recently") {
+        let left = body
+            .split_once("You have ")
+            .and_then(|(_, rest)| rest.split_once(" left to wait"))
+            .map_or("a while", |(left, _)| left);
+        Outcome::TooSoon(left.to_string())
+    } else if body.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        anyhow::bail! {"could not make sense of the response to the answer"};
+    };
+    Ok(outcome)
+}
+
+/// Submits `answer` for one part of the input whose known answers live in
+/// `output`, keeping every verdict in the log next to it. An answer that is
+/// already known to be right or wrong isn't sent again, and nor is one that
+/// the log's too high and too low bounds rule out.
+pub fn submit(
+    site: &mut Site,
+    day: usize,
+    part: u8,
+    answer: &str,
+    output: &Path,
+) -> anyhow::Result<Outcome> {
+    let mut expected = Expected::load(output)?;
+    let log_path = output.with_extension("log");
+    let mut log = Log::load(&log_path)?;
+    match expected.get(part) {
+        Some(known) if known == answer => return Ok(Outcome::Known(Verdict::Right)),
+        Some(known) => anyhow::bail! {
+            "part {part} is already solved as {known}, according to {}",
+            output.display()
+        },
+        None => {}
+    }
+    if let Some(verdict) = log.verdict(part, answer) {
+        return Ok(Outcome::Known(verdict));
+    }
+    if let Some(reason) = log.disproves(part, answer) {
+        return Ok(Outcome::Disproved(reason));
+    }
+
+    let outcome = site.answer(day, part, answer)?;
+    if let Outcome::Judged(verdict) = outcome {
+        log.add(part, verdict, answer);
+        log.save(&log_path)?;
+        if verdict == Verdict::Right {
+            expected.record(part, answer)?;
+            expected.save(output)?;
+        }
+    }
+    Ok(outcome)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+/// Whether an input has already been downloaded to `path`. An empty file
+/// doesn't count, since that is what a failed download can leave behind.
+pub fn is_cached(path: &Path) -> bool {
+    std::fs::metadata(path).is_ok_and(|m| m.len() > 0)
+}
+
+/// Downloads the day's input to `path`, unless it's already there: an input
+/// never changes, so an existing one is never requested again.
+pub fn fetch(site: &mut Site, day: usize, path: &Path) -> anyhow::Result<Fetched> {
+    if is_cached(path) {
+        return Ok(Fetched::Cached);
+    }
+    let input = site.input(day)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format! {"could not create {}", dir.display()})?;
+    }
+    std::fs::write(path, input).with_context(|| format! {"could not write {}", path.display()})?;
+    Ok(Fetched::Downloaded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{PlainHttp, testing::Server};
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format! {"aoc2025_rs-{name}-{}", std::process::id()});
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn it_needs_a_session_cookie() -> anyhow::Result<()> {
+        let dir = temp_dir("session");
+        let cookie = dir.join(".cookie");
+        assert! {session_from(None, &cookie).unwrap_err().to_string().contains("no session cookie")};
+        std::fs::write(&cookie, "\n")?;
+        assert! {session_from(None, &cookie).unwrap_err().to_string().contains("is empty")};
+        std::fs::write(&cookie, "abc123\n")?;
+        assert_eq! {session_from(None, &cookie)?, "abc123"};
+        assert_eq! {session_from(Some("def".into()), &cookie)?, "def"};
+        std::fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn it_downloads_each_input_once() -> anyhow::Result<()> {
+        let dir = temp_dir("fetch");
+        let server = Server::start(vec![(200, "1,2\n3,4\n")]);
+        let mut site = Site::new(
+            Box::new(PlainHttp),
+            &server.url,
+            "abc".into(),
+            Duration::ZERO,
+        );
+
+        let path = dir.join("day09/joshua.input");
+        std::fs::create_dir_all(dir.join("day09"))?;
+        std::fs::write(&path, "")?;
+        assert! {!is_cached(&path)};
+        assert_eq! {fetch(&mut site, 9, &path)?, Fetched::Downloaded};
+        assert! {is_cached(&path)};
+        assert_eq! {std::fs::read_to_string(&path)?, "1,2\n3,4\n"};
+        assert_eq! {fetch(&mut site, 9, &path)?, Fetched::Cached};
+
+        let requests = server.finish();
+        assert_eq! {requests.len(), 1};
+        assert! {requests[0].starts_with("GET /2025/day/9/input ")};
+        std::fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn it_reports_an_expired_cookie() -> anyhow::Result<()> {
+        let server = Server::start(vec![
+            (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+            ),
+            (
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks!",
+            ),
+            (502, "Bad Gateway"),
+        ]);
+        let mut site = Site::new(
+            Box::new(PlainHttp),
+            &server.url,
+            "old".into(),
+            Duration::ZERO,
+        );
+        assert! {site.input(1).unwrap_err().to_string().contains("expired")};
+        assert! {site.input(25).unwrap_err().to_string().contains("isn't unlocked yet")};
+        assert! {site.input(2).unwrap_err().to_string().contains("server error (502)")};
+        server.finish();
+        Ok(())
+    }
+
+    #[test]
+    fn it_waits_between_requests() -> anyhow::Result<()> {
+        let server = Server::start(vec![(200, "a"), (200, "b")]);
+        let interval = Duration::from_millis(200);
+        let mut site = Site::new(Box::new(PlainHttp), &server.url, "abc".into(), interval);
+        let start = Instant::now();
+        site.input(1)?;
+        site.input(2)?;
+        assert! {start.elapsed() >= interval};
+        server.finish();
+        Ok(())
+    }
+
+    #[test]
+    fn it_understands_the_responses() -> anyhow::Result<()> {
+        let parse = |body| parse_outcome(body).unwrap();
+        assert_eq! {
+            parse("<p>That's the right answer!  You are one gold star closer.</p>"),
+            Outcome::Judged(Verdict::Right)
+        };
+        assert_eq! {
+            parse("<p>That's not the right answer; your answer is too high.  If you're stuck...</p>"),
+            Outcome::Judged(Verdict::TooHigh)
+        };
+        assert_eq! {
+            parse("<p>That's not the right answer; your answer is too low.</p>"),
+            Outcome::Judged(Verdict::TooLow)
+        };
+        assert_eq! {
+            parse("<p>That's not the right answer.  If you're stuck...</p>"),
+            Outcome::Judged(Verdict::Wrong)
+        };
+        assert_eq! {
+            parse("<p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 41s left to wait.</p>"),
+            Outcome::TooSoon("41s".to_string())
+        };
+        assert_eq! {
+            parse("<p>You don't seem to be solving the right level.  Did you already complete it?</p>"),
+            Outcome::WrongLevel
+        };
+        assert! {parse_outcome("<html></html>").is_err()};
+        Ok(())
+    }
+
+    #[test]
+    fn it_never_resubmits_a_known_answer() -> anyhow::Result<()> {
+        let dir = temp_dir("submit");
+        let output = dir.join("joshua.output");
+        let server = Server::start(vec![
+            (
+                200,
+                "<p>That's not the right answer; your answer is too high.</p>",
+            ),
+            (200, "<p>That's the right answer!</p>"),
+        ]);
+        let mut site = Site::new(
+            Box::new(PlainHttp),
+            &server.url,
+            "abc".into(),
+            Duration::ZERO,
+        );
+
+        assert_eq! {submit(&mut site, 1, 1, "1000", &output)?, Outcome::Judged(Verdict::TooHigh)};
+        assert_eq! {submit(&mut site, 1, 1, "1000", &output)?, Outcome::Known(Verdict::TooHigh)};
+        assert_eq! {submit(&mut site, 1, 1, "962", &output)?, Outcome::Judged(Verdict::Right)};
+        assert_eq! {submit(&mut site, 1, 1, "962", &output)?, Outcome::Known(Verdict::Right)};
+        assert! {submit(&mut site, 1, 1, "963", &output).is_err()};
+
+        assert_eq! {Expected::load(&output)?.get(1), Some("962")};
+        assert_eq! {
+            std::fs::read_to_string(dir.join("joshua.log"))?,
+            "1 too-high 1000\n1 right 962\n"
+        };
+        let requests = server.finish();
+        assert_eq! {requests.len(), 2};
+        assert! {requests[0].starts_with("POST /2025/day/1/answer ")};
+        assert! {requests[0].ends_with("level=1&answer=1000")};
+        std::fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn it_never_submits_a_disproved_answer() -> anyhow::Result<()> {
+        let dir = temp_dir("disproved");
+        let output = dir.join("joshua.output");
+        std::fs::write(dir.join("joshua.log"), "2 too-high 1000\n2 too-low 10\n")?;
+        let server = Server::start(vec![(200, "<p>That's the right answer!</p>")]);
+        let mut site = Site::new(
+            Box::new(PlainHttp),
+            &server.url,
+            "abc".into(),
+            Duration::ZERO,
+        );
+
+        assert_eq! {
+            submit(&mut site, 1, 2, "1001", &output)?,
+            Outcome::Disproved("1001 is at least 1000, which is too high".into())
+        };
+        assert_eq! {
+            submit(&mut site, 1, 2, "5", &output)?,
+            Outcome::Disproved("5 is at most 10, which is too low".into())
+        };
+        assert_eq! {submit(&mut site, 1, 2, "500", &output)?, Outcome::Judged(Verdict::Right)};
+
+        let requests = server.finish();
+        assert_eq! {requests.len(), 1};
+        assert! {requests[0].ends_with("level=2&answer=500")};
+        std::fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+use std::time::{Duration, Instant};
+
+use crate::params::Params;
+use crate::registry::Constructor;
+use crate::runner::{self, Input, Part};
+use crate::table;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort();
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+        let mean = samples.iter().map(Duration::as_secs_f64).sum::<f64>() / n as f64;
+        let variance = samples
+            .iter()
+            .map(|s| (s.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        Some(Stats {
+            samples: n,
+            min: samples[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+pub struct Options {
+    /// Stop after this many timed runs of each phase.
+    pub runs: usize,
+    /// Or once a phase has used this much time, whichever comes first.
+    pub budget: Duration,
+}
+
+pub struct DayBench {
+    pub day: usize,
+    pub parse: anyhow::Result<Stats>,
+    pub parts: Vec<(u8, anyhow::Result<Stats>)>,
+}
+
+/// Times `f` on fresh values from `setup` until the run or time budget is used
+/// up, after one untimed warm-up run that also surfaces any error. A panic in
+/// any run is reported as an error rather than ending the whole bench.
+fn sample<I, T>(
+    options: &Options,
+    mut setup: impl FnMut() -> I,
+    mut f: impl FnMut(I) -> anyhow::Result<T>,
+) -> anyhow::Result<Stats> {
+    let warm_up = setup();
+    runner::guarded(|| f(warm_up))?;
+    let mut samples = Vec::new();
+    let started = Instant::now();
+    while samples.len() < options.runs.max(1)
+        && (samples.is_empty() || started.elapsed() < options.budget)
+    {
+        let value = setup();
+        let start = Instant::now();
+        let result = runner::guarded(|| f(value));
+        samples.push(start.elapsed());
+        result?;
+    }
+    Ok(Stats::from_samples(&mut samples).expect("at least one sample"))
+}
+
+pub fn bench(
+    day: usize,
+    part: Part,
+    lines: &[String],
+    create: Constructor,
+    params: &Params,
+    options: &Options,
+) -> DayBench {
+    let input = || -> Input { Box::new(Vec::from(lines).into_iter()) };
+    let parse = sample(options, input, |input| {
+        runner::construct(create, input, params)
+    });
+    if parse.is_err() {
+        return DayBench {
+            day,
+            parse,
+            parts: Vec::new(),
+        };
+    }
+    let solver = match runner::construct(create, input(), params) {
+        Ok(solver) => solver,
+        Err(e) => {
+            return DayBench {
+                day,
+                parse: Err(e),
+                parts: Vec::new(),
+            };
+        }
+    };
+
+    let parts = part
+        .numbers()
+        .iter()
+        .map(|&part| {
+            let stats = sample(options, || (), |_| runner::solve(solver.as_ref(), part));
+            (part, stats)
+        })
+        .collect();
+    DayBench { day, parse, parts }
+}
+
+pub fn print_table(results: &[DayBench]) {
+    let header = [
+        "Day", "Phase", "Runs", "Min", "Median", "Mean", "Stddev", "Error",
+    ];
+    let mut rows = Vec::new();
+    for result in results {
+        let phases = std::iter::once(("parse".to_string(), &result.parse)).chain(
+            result
+                .parts
+                .iter()
+                .map(|(part, stats)| (format! {"part {part}"}, stats)),
+        );
+        for (phase, stats) in phases {
+            let mut row = vec![format! {"{:02}", result.day}, phase];
+            match stats {
+                Ok(stats) => {
+                    row.push(stats.samples.to_string());
+                    for d in [stats.min, stats.median, stats.mean, stats.stddev] {
+                        row.push(format! {"{d:.2?}"});
+                    }
+                }
+                Err(e) => {
+                    row.extend(["-", "-", "-", "-", "-"].map(String::from));
+                    row.push(format! {"{e:#}"});
+                }
+            }
+            rows.push(row);
+        }
+    }
+
+    table::print(&header, &rows);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_computes_stats() {
+        let mut samples = [4, 1, 3, 2].map(Duration::from_millis);
+        let stats = Stats::from_samples(&mut samples).unwrap();
+        assert_eq! {stats.samples, 4};
+        assert_eq! {stats.min, Duration::from_millis(1)};
+        assert_eq! {stats.median, Duration::from_micros(2500)};
+        assert_eq! {stats.mean.as_micros(), 2500};
+        // sqrt(1.25) ms
+        assert_eq! {stats.stddev.as_micros(), 1118};
+        assert! {Stats::from_samples(&mut []).is_none()};
+    }
+}
@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+/// Runs `f`, returning the CPU time this thread spent on it. Unlike wall time
+/// that leaves out time spent waiting for a busy CPU, so it doesn't grow with
+/// the number of threads sharing one. Only measured on 64-bit Linux, and
+/// `None` elsewhere.
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Option<Duration>) {
+    let start = thread_time();
+    let result = f();
+    let spent = start
+        .zip(thread_time())
+        .map(|(start, end)| end.saturating_sub(start));
+    (result, spent)
+}
+
+#[cfg(all(target_os = "linux", target_pointer_width = "64"))]
+fn thread_time() -> Option<Duration> {
+    #[repr(C)]
+    struct Timespec {
+        tv_sec: i64,
+        tv_nsec: i64,
+    }
+
+    const CLOCK_THREAD_CPUTIME_ID: i32 = 3;
+
+    unsafe extern "C" {
+        fn clock_gettime(clock: i32, now: *mut Timespec) -> i32;
+    }
+
+    let mut now = Timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    if unsafe { clock_gettime(CLOCK_THREAD_CPUTIME_ID, &mut now) } != 0 {
+        return None;
+    }
+    Some(Duration::new(now.tv_sec as u64, now.tv_nsec as u32))
+}
+
+#[cfg(not(all(target_os = "linux", target_pointer_width = "64")))]
+fn thread_time() -> Option<Duration> {
+    None
+}
+
+#[cfg(all(test, target_os = "linux", target_pointer_width = "64"))]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn it_only_counts_time_on_the_cpu() {
+        let ((), asleep) = time(|| std::thread::sleep(Duration::from_millis(100)));
+        assert! {asleep.unwrap() < Duration::from_millis(50), "{asleep:?}"};
+
+        let ((), busy) = time(|| {
+            let start = Instant::now();
+            while start.elapsed() < Duration::from_millis(100) {
+                std::hint::spin_loop();
+            }
+        });
+        assert! {busy.unwrap() > Duration::from_millis(20), "{busy:?}"};
+    }
+}
@@ -1,10 +1,8 @@
-extern crate test;
-
-use anyhow::Context;
-use itertools::Itertools;
-
 use solver::{Solver, SolverToAny};
 
+use crate::params::Params;
+use crate::parse::{self, Cursor};
+
 pub struct Day01 {
     instructions: Vec<Instruction>,
     start: u16,
@@ -21,29 +19,30 @@ enum Instruction {
     Right(u16),
 }
 
-impl TryFrom<String> for Instruction {
-    type Error = anyhow::Error;
-
-    fn try_from(value: String) -> anyhow::Result<Instruction> {
-        let step = value
-            .trim_start_matches(['L', 'R'])
-            .parse::<u16>()
-            .with_context(|| format! {"could not parse {value}"})?;
-        if value.starts_with('R') {
-            Ok(Instruction::Right(step))
-        } else if value.starts_with('L') {
-            Ok(Instruction::Left(step))
+impl Instruction {
+    fn parse(line: &mut Cursor) -> anyhow::Result<Instruction> {
+        if line.eat("L") {
+            Ok(Instruction::Left(line.number()?))
+        } else if line.eat("R") {
+            Ok(Instruction::Right(line.number()?))
         } else {
-            anyhow::bail! {"{value} doesn't start with l or r!"}
+            Err(line.expected("L or R"))
         }
     }
 }
 
 impl Day01 {
-    pub fn try_create(input: Box<dyn Iterator<Item = String>>) -> anyhow::Result<Box<dyn Solver>> {
+    pub fn try_create(
+        input: Box<dyn Iterator<Item = String>>,
+        params: &Params,
+    ) -> anyhow::Result<Box<dyn Solver + Send + Sync>> {
+        let start = params.get_or("start", 50)?;
+        if start >= 100 {
+            anyhow::bail! {"parameter start is {start}, but the dial only goes up to 99"};
+        }
         Ok(Box::new(Day01 {
-            instructions: input.map(Instruction::try_from).try_collect()?,
-            start: 50,
+            instructions: parse::lines(input, Instruction::parse)?,
+            start,
         }))
     }
 }
@@ -102,75 +101,22 @@ impl Solver for Day01 {
 
 #[cfg(test)]
 mod tests {
-    use std::error::Error;
-
     use super::*;
-    use test::Bencher;
-
-    #[test]
-    fn it_works_on_the_example() -> Result<(), Box<dyn Error>> {
-        let input = include_str!("../puzzles/day01/example.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day01::try_create(Box::new(input)).unwrap();
-        assert! {solver.part_one().is_ok_and(|s| s == "3")};
-        Ok(())
-    }
-
-    #[test]
-    fn it_works_on_the_other_example() -> Result<(), Box<dyn Error>> {
-        let input = include_str!("../puzzles/day01/example.input")
-            .lines()
-            .map(String::from);
 
-        let solver = Day01::try_create(Box::new(input)).unwrap();
-        assert_eq! {solver.part_two()?, "6"};
-        Ok(())
+    crate::macros::solver_tests! {
+        Day01, "day01", bench: joshua;
+        example: { part_one: "3", part_two: "6" },
+        joshua: { part_one: "962", part_two: "5782" },
     }
 
     #[test]
-    fn it_works_on_my_input() -> Result<(), Box<dyn Error>> {
-        let input = include_str!("../puzzles/day01/joshua.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day01::try_create(Box::new(input)).unwrap();
-        assert_eq! {solver.part_one()?, "962"};
-        assert_eq! {solver.part_two()?, "5782"};
-        Ok(())
-    }
-
-    #[bench]
-    fn bench_parse(b: &mut Bencher) {
-        b.iter(|| {
-            let input = include_str!("../puzzles/day01/joshua.input")
-                .lines()
-                .map(String::from);
-
-            let _solver = Day01::try_create(Box::new(input)).unwrap();
-        });
-    }
-
-    #[bench]
-    fn bench_one(b: &mut Bencher) {
-        let input = include_str!("../puzzles/day01/joshua.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day01::try_create(Box::new(input)).unwrap();
-
-        b.iter(|| solver.part_one());
-    }
-
-    #[bench]
-    fn bench_two(b: &mut Bencher) {
-        let input = include_str!("../puzzles/day01/joshua.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day01::try_create(Box::new(input)).unwrap();
-
-        b.iter(|| solver.part_two());
+    fn it_rejects_starts_off_the_dial() {
+        let start = |value: &str| {
+            let params = Params::from_iter([("start".to_string(), value.to_string())]);
+            Day01::try_create(Box::new(std::iter::empty()), &params).map(|_| ())
+        };
+        assert! {start("99").is_ok()};
+        assert! {start("100").is_err()};
+        assert! {start("65535").is_err()};
     }
 }
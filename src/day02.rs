@@ -1,11 +1,14 @@
-extern crate test;
-
 use std::ops::RangeInclusive;
 
 use itertools::Itertools;
 use solver::{Solver, SolverToAny};
 
+use crate::interval;
+use crate::params::Params;
+use crate::parse;
+
 pub struct Day02 {
+    /// Kept as given rather than merged, so an id in two ranges counts twice.
     ranges: Vec<RangeInclusive<u64>>,
 }
 
@@ -16,22 +19,20 @@ impl SolverToAny for Day02 {
 }
 
 impl Day02 {
-    pub fn try_create(input: Box<dyn Iterator<Item = String>>) -> anyhow::Result<Box<dyn Solver>> {
+    pub fn try_create(
+        input: Box<dyn Iterator<Item = String>>,
+        _params: &Params,
+    ) -> anyhow::Result<Box<dyn Solver + Send + Sync>> {
         let mut ranges = Vec::new();
-        for line in input {
-            for group in line.split(',') {
-                if let Some((l, r)) = group.split_once('-') {
-                    let left = l.parse::<u64>();
-                    let right = r.parse::<u64>();
-                    match (left, right) {
-                        (Ok(pl), Ok(pr)) => ranges.push(pl..=pr),
-                        (l, r) => {
-                            panic! {"could not parse {l:?} or {r:?}"}
-                        }
-                    }
+        parse::lines(input, |line| {
+            loop {
+                ranges.push(interval::parse_range(line)?);
+                if !line.eat(",") || line.rest().trim().is_empty() {
+                    line.skip_spaces();
+                    return Ok(());
                 }
             }
-        }
+        })?;
 
         Ok(Box::new(Day02 { ranges }))
     }
@@ -95,75 +96,19 @@ impl Solver for Day02 {
 
 #[cfg(test)]
 mod tests {
-    use std::error::Error;
-
     use super::*;
-    use test::Bencher;
-
-    #[test]
-    fn it_works_on_the_example() -> Result<(), Box<dyn Error>> {
-        let input = include_str!("../puzzles/day02/example.input")
-            .lines()
-            .map(String::from);
 
-        let solver = Day02::try_create(Box::new(input)).unwrap();
-        assert_eq! {solver.part_one()?, "1227775554"};
-        Ok(())
-    }
-
-    #[test]
-    fn it_works_on_the_other_example() -> Result<(), Box<dyn Error>> {
-        let input = include_str!("../puzzles/day02/example.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day02::try_create(Box::new(input)).unwrap();
-        assert_eq! {solver.part_two()?, "4174379265"};
-        Ok(())
+    crate::macros::solver_tests! {
+        Day02, "day02", bench: joshua;
+        example: { part_one: "1227775554", part_two: "4174379265" },
+        joshua: { part_one: "40398804950", part_two: "65794984339" },
     }
 
     #[test]
-    fn it_works_on_my_input() -> Result<(), Box<dyn Error>> {
-        let input = include_str!("../puzzles/day02/joshua.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day02::try_create(Box::new(input)).unwrap();
-        assert_eq! {solver.part_one()?, "40398804950"};
-        assert_eq! {solver.part_two()?, "65794984339"};
+    fn it_sums_each_range_on_its_own() -> anyhow::Result<()> {
+        let input = Box::new(["11-22,15-33".to_string()].into_iter());
+        let solver = Day02::try_create(input, &Params::default())?;
+        assert_eq! {solver.part_one()?, (11 + 22 + 22 + 33).to_string()};
         Ok(())
     }
-
-    #[bench]
-    fn bench_parse(b: &mut Bencher) {
-        b.iter(|| {
-            let input = include_str!("../puzzles/day02/joshua.input")
-                .lines()
-                .map(String::from);
-
-            let _solver = Day02::try_create(Box::new(input)).unwrap();
-        });
-    }
-
-    #[bench]
-    fn bench_one(b: &mut Bencher) {
-        let input = include_str!("../puzzles/day02/joshua.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day02::try_create(Box::new(input)).unwrap();
-
-        b.iter(|| solver.part_one());
-    }
-
-    #[bench]
-    fn bench_two(b: &mut Bencher) {
-        let input = include_str!("../puzzles/day02/joshua.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day02::try_create(Box::new(input)).unwrap();
-
-        b.iter(|| solver.part_two());
-    }
 }
@@ -1,7 +1,8 @@
-extern crate test;
-
 use solver::{Solver, SolverToAny};
 
+use crate::params::Params;
+use crate::parse;
+
 pub struct Day03 {
     joltages: Vec<Vec<u8>>,
 }
@@ -13,11 +14,18 @@ impl SolverToAny for Day03 {
 }
 
 impl Day03 {
-    pub fn try_create(input: Box<dyn Iterator<Item = String>>) -> anyhow::Result<Box<dyn Solver>> {
+    pub fn try_create(
+        input: Box<dyn Iterator<Item = String>>,
+        _params: &Params,
+    ) -> anyhow::Result<Box<dyn Solver + Send + Sync>> {
         Ok(Box::new(Day03 {
-            joltages: input
-                .map(|line| line.chars().map(|c| c as u8 - b'0').collect::<Vec<u8>>())
-                .collect::<Vec<_>>(),
+            joltages: parse::lines(input, |line| {
+                let mut joltages = Vec::new();
+                while !line.is_empty() {
+                    joltages.push(line.digit()?);
+                }
+                Ok(joltages)
+            })?,
         }))
     }
 
@@ -62,75 +70,11 @@ impl Solver for Day03 {
 
 #[cfg(test)]
 mod tests {
-    use std::error::Error;
-
     use super::*;
-    use test::Bencher;
-
-    #[test]
-    fn it_works_on_the_example() -> Result<(), Box<dyn Error>> {
-        let input = include_str!("../puzzles/day03/example.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day03::try_create(Box::new(input)).unwrap();
-        assert_eq! {solver.part_one()?, "357"};
-        Ok(())
-    }
-
-    #[test]
-    fn it_works_on_the_other_example() -> Result<(), Box<dyn Error>> {
-        let input = include_str!("../puzzles/day03/example.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day03::try_create(Box::new(input)).unwrap();
-        assert_eq! {solver.part_two()?, "3121910778619"};
-        Ok(())
-    }
-
-    #[test]
-    fn it_works_on_my_input() -> Result<(), Box<dyn Error>> {
-        let input = include_str!("../puzzles/day03/joshua.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day03::try_create(Box::new(input)).unwrap();
-        assert_eq! {solver.part_one()?, "17430"};
-        assert_eq! {solver.part_two()?, "171975854269367"};
-        Ok(())
-    }
-
-    #[bench]
-    fn bench_parse(b: &mut Bencher) {
-        b.iter(|| {
-            let input = include_str!("../puzzles/day03/joshua.input")
-                .lines()
-                .map(String::from);
-
-            let _solver = Day03::try_create(Box::new(input)).unwrap();
-        });
-    }
-
-    #[bench]
-    fn bench_one(b: &mut Bencher) {
-        let input = include_str!("../puzzles/day03/joshua.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day03::try_create(Box::new(input)).unwrap();
-
-        b.iter(|| solver.part_one());
-    }
-
-    #[bench]
-    fn bench_two(b: &mut Bencher) {
-        let input = include_str!("../puzzles/day03/joshua.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day03::try_create(Box::new(input)).unwrap();
 
-        b.iter(|| solver.part_two());
+    crate::macros::solver_tests! {
+        Day03, "day03", bench: joshua;
+        example: { part_one: "357", part_two: "3121910778619" },
+        joshua: { part_one: "17430", part_two: "171975854269367" },
     }
 }
@@ -1,12 +1,15 @@
-extern crate test;
-
-use anyhow::Context;
 use itertools::Itertools;
 use solver::{Solver, SolverToAny};
 use std::fmt::{Display, Formatter};
 
+use crate::params::Params;
+use crate::parse::{self, Cursor};
+
 pub struct Day06 {
-    input: Vec<String>,
+    /// The questions as part one reads them, one number per line.
+    rows: Vec<Question>,
+    /// The questions as part two reads them, one number per column of digits.
+    columns: Vec<Question>,
 }
 
 struct Question {
@@ -34,7 +37,7 @@ impl Display for Question {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 enum Op {
     Mul,
     Add,
@@ -47,44 +50,138 @@ impl SolverToAny for Day06 {
 }
 
 impl Day06 {
-    pub fn try_create(input: Box<dyn Iterator<Item = String>>) -> anyhow::Result<Box<dyn Solver>> {
+    pub fn try_create(
+        input: Box<dyn Iterator<Item = String>>,
+        _params: &Params,
+    ) -> anyhow::Result<Box<dyn Solver + Send + Sync>> {
+        let [worksheet] = parse::sections(input, ["worksheet"])?;
+        let lines = worksheet.lines().collect_vec();
+        let (&(number, op_line), number_lines) =
+            lines.split_last().expect("a section has at least one line");
+
+        let mut line = Cursor::new(number, op_line);
+        let mut ops = Vec::new();
+        while !skip_spaces(&mut line) {
+            ops.push(Op::parse(&mut line)?);
+        }
+        let mut numbers = Vec::new();
+        for &(number, text) in number_lines {
+            let mut line = Cursor::new(number, text);
+            let mut ns = Vec::new();
+            while !skip_spaces(&mut line) {
+                ns.push(line.number()?);
+            }
+            if ns.len() != ops.len() {
+                return Err(line.error(format! {
+                    "found {} numbers, but there are {} operators", ns.len(), ops.len()
+                }));
+            }
+            numbers.push(ns);
+        }
+
+        let rows = ops
+            .iter()
+            .enumerate()
+            .map(|(idx, &op)| Question {
+                op,
+                numbers: numbers.iter().map(|ns| ns[idx]).collect(),
+            })
+            .collect();
         Ok(Box::new(Day06 {
-            input: input.filter(|s| !s.is_empty()).collect_vec(),
+            rows,
+            columns: columns(number_lines, op_line)?,
         }))
     }
 }
 
-impl Solver for Day06 {
-    fn part_one(&self) -> anyhow::Result<String> {
-        let mut numbers = Vec::new();
-        let mut ops = Vec::new();
+/// Skips to the next entry on the line, saying whether the line is done.
+fn skip_spaces(line: &mut Cursor) -> bool {
+    line.skip_spaces();
+    line.is_empty()
+}
 
-        for line in &self.input {
-            let mut ns = Vec::new();
-            for entry in line.split_ascii_whitespace() {
-                match entry {
-                    "*" => ops.push(Op::Mul),
-                    "+" => ops.push(Op::Add),
-                    s => {
-                        ns.push(s.parse::<u64>().context(format! {"could not parse {s}"})?);
-                    }
-                }
-            }
-            if !ns.is_empty() {
-                numbers.push(ns);
-            }
+impl Op {
+    fn parse(line: &mut Cursor) -> anyhow::Result<Op> {
+        if line.eat("*") {
+            Ok(Op::Mul)
+        } else if line.eat("+") {
+            Ok(Op::Add)
+        } else {
+            Err(line.expected("* or +"))
         }
+    }
+}
 
-        let mut questions = Vec::new();
-        for (idx, op) in ops.into_iter().enumerate() {
-            let mut ns = Vec::new();
-            for item in &numbers {
-                ns.push(item[idx]);
+/// The questions read down the columns, each column of digits one number, and
+/// questions separated by a column of spaces. The operator line has already
+/// been read, so holds only operators and spaces.
+fn columns(number_lines: &[(usize, &str)], op_line: &str) -> anyhow::Result<Vec<Question>> {
+    for &(number, text) in number_lines {
+        let mut line = Cursor::new(number, text);
+        while let Some(c) = line.peek() {
+            if c != ' ' && !c.is_ascii_digit() {
+                return Err(line.expected("a digit or a space"));
             }
-            questions.push(Question { op, numbers: ns });
+            line.char()?;
         }
+    }
+    let number_lines = number_lines.iter().map(|&(_, text)| text).collect_vec();
+    let at = |line: &str, i: usize| line.as_bytes().get(i).copied().unwrap_or(b' ');
+    let width = number_lines
+        .iter()
+        .chain([&op_line])
+        .map(|l| l.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut questions = Vec::new();
+    let mut numbers = Vec::new();
+    let mut op = Op::Mul;
+    for i in 0..width {
+        if number_lines
+            .iter()
+            .chain([&op_line])
+            .all(|l| at(l, i) == b' ')
+        {
+            questions.push(Question { op, numbers });
+            numbers = Vec::new();
+            op = Op::Mul;
+            continue;
+        }
+        match at(op_line, i) {
+            b'*' => op = Op::Mul,
+            b'+' => op = Op::Add,
+            _ => {}
+        }
+        numbers.push(
+            number_lines
+                .iter()
+                .filter(|l| at(l, i) != b' ')
+                .fold(0, |number, l| number * 10 + u64::from(at(l, i) - b'0')),
+        );
+    }
+    questions.push(Question { op, numbers });
+    Ok(questions)
+}
+
+impl Display for Day06 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln! {f, "rows:"}?;
+        for question in &self.rows {
+            writeln! {f, "{question}"}?;
+        }
+        writeln! {f, "columns:"}?;
+        for question in &self.columns {
+            writeln! {f, "{question}"}?;
+        }
+        Ok(())
+    }
+}
 
-        Ok(questions
+impl Solver for Day06 {
+    fn part_one(&self) -> anyhow::Result<String> {
+        Ok(self
+            .rows
             .iter()
             .map(Question::answer)
             .sum::<u64>()
@@ -92,51 +189,8 @@ impl Solver for Day06 {
     }
 
     fn part_two(&self) -> anyhow::Result<String> {
-        let mut questions = Vec::new();
-
-        let n = self.input[0].len();
-        let chars = self
-            .input
-            .iter()
-            .map(|l| (l.bytes().collect_vec(), l.starts_with(['*', '+'])))
-            .collect_vec();
-        let mut numbers = Vec::new();
-        let mut op = Op::Mul;
-
-        let mut number = 0_u64;
-        for i in 0..n {
-            if chars.iter().all(|c| c.0[i].is_ascii_whitespace()) {
-                questions.push(Question { op, numbers });
-                numbers = Vec::new();
-                op = Op::Mul;
-                continue;
-            }
-            for (line, is_op) in &chars {
-                if !is_op {
-                    let c = line[i];
-                    match c {
-                        b'0'..=b'9' => {
-                            number = (number * 10) + (c - b'0') as u64;
-                        }
-                        b' ' => {}
-                        c => panic! {"did not expect {c}"},
-                    }
-                } else {
-                    match line[i] {
-                        b'*' => op = Op::Mul,
-                        b'+' => op = Op::Add,
-                        b' ' => {}
-                        c => panic! {"did not expect {c:?}"},
-                    }
-                }
-            }
-            numbers.push(number);
-            number = 0;
-        }
-
-        questions.push(Question { op, numbers });
-
-        Ok(questions
+        Ok(self
+            .columns
             .iter()
             .map(Question::answer)
             .sum::<u64>()
@@ -146,75 +200,29 @@ impl Solver for Day06 {
 
 #[cfg(test)]
 mod tests {
-    use std::error::Error;
-
     use super::*;
-    use test::Bencher;
-
-    #[test]
-    fn it_works_on_the_example() -> Result<(), Box<dyn Error>> {
-        let input = include_str!("../puzzles/day06/example.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day06::try_create(Box::new(input)).unwrap();
-        assert_eq! {solver.part_one()?, "4277556"};
-        Ok(())
-    }
-
-    #[test]
-    fn it_works_on_the_other_example() -> Result<(), Box<dyn Error>> {
-        let input = include_str!("../puzzles/day06/example.input")
-            .lines()
-            .map(String::from);
 
-        let solver = Day06::try_create(Box::new(input)).unwrap();
-        assert_eq! {solver.part_two()?, "3263827"};
-        Ok(())
+    crate::macros::solver_tests! {
+        Day06, "day06", bench: joshua;
+        example: { part_one: "4277556", part_two: "3263827" },
+        joshua: { part_one: "5873191732773", part_two: "11386445308378" },
     }
 
     #[test]
-    fn it_works_on_my_input() -> Result<(), Box<dyn Error>> {
-        let input = include_str!("../puzzles/day06/joshua.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day06::try_create(Box::new(input)).unwrap();
-        assert_eq! {solver.part_one()?, "5873191732773"};
-        assert_eq! {solver.part_two()?, "11386445308378"};
-        Ok(())
-    }
-
-    #[bench]
-    fn bench_parse(b: &mut Bencher) {
-        b.iter(|| {
-            let input = include_str!("../puzzles/day06/joshua.input")
-                .lines()
-                .map(String::from);
-
-            let _solver = Day06::try_create(Box::new(input)).unwrap();
-        });
-    }
-
-    #[bench]
-    fn bench_one(b: &mut Bencher) {
-        let input = include_str!("../puzzles/day06/joshua.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day06::try_create(Box::new(input)).unwrap();
-
-        b.iter(|| solver.part_one());
-    }
-
-    #[bench]
-    fn bench_two(b: &mut Bencher) {
-        let input = include_str!("../puzzles/day06/joshua.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day06::try_create(Box::new(input)).unwrap();
-
-        b.iter(|| solver.part_two());
+    fn it_says_where_the_worksheet_went_wrong() {
+        let error = |text: &str| {
+            let input = Box::new(text.lines().map(String::from).collect_vec().into_iter());
+            Day06::try_create(input, &Params::default())
+                .err()
+                .map(|e| e.to_string())
+        };
+        assert_eq! {
+            error("12\t3\n*  +"),
+            Some("line 1, column 3: expected a number, found '\\t'".to_string())
+        };
+        assert_eq! {
+            columns(&[(1, "12 3"), (2, " 4x5")], "*  +").err().map(|e| e.to_string()),
+            Some("line 2, column 3: expected a digit or a space, found \"x5\"".to_string())
+        };
     }
 }
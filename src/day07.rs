@@ -1,13 +1,13 @@
-extern crate test;
-
 use anyhow::bail;
-use itertools::Itertools;
 use solver::{Solver, SolverToAny};
 use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 
+use crate::grid::Grid;
+use crate::params::Params;
+
 pub struct Day07 {
-    map: Vec<Vec<Space>>,
+    map: Grid<Space>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -41,6 +41,12 @@ impl Display for Space {
     }
 }
 
+impl Display for Day07 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write! {f, "{}", self.map}
+    }
+}
+
 impl SolverToAny for Day07 {
     fn as_any(&self) -> &dyn std::any::Any {
         self
@@ -48,11 +54,12 @@ impl SolverToAny for Day07 {
 }
 
 impl Day07 {
-    pub fn try_create(input: Box<dyn Iterator<Item = String>>) -> anyhow::Result<Box<dyn Solver>> {
+    pub fn try_create(
+        input: Box<dyn Iterator<Item = String>>,
+        _params: &Params,
+    ) -> anyhow::Result<Box<dyn Solver + Send + Sync>> {
         Ok(Box::new(Day07 {
-            map: input
-                .map(|line| line.chars().map(Space::try_from).try_collect())
-                .try_collect()?,
+            map: Grid::parse(input)?,
         }))
     }
 }
@@ -60,14 +67,14 @@ impl Day07 {
 impl Solver for Day07 {
     fn part_one(&self) -> anyhow::Result<String> {
         let mut beams = HashSet::new();
-        let Some(x) = self.map[0].iter().position(|s| *s == Space::Source) else {
+        let Some(x) = self.map.row(0).iter().position(|s| *s == Space::Source) else {
             panic! {"first line did not have start point"}
         };
         beams.insert(x);
 
         let mut splits = 0;
         let mut empty_only = false;
-        for row in self.map.iter().skip(1) {
+        for row in self.map.rows().skip(1) {
             empty_only = !empty_only;
             let mut new_beams = HashSet::new();
             for beam in beams {
@@ -94,13 +101,13 @@ impl Solver for Day07 {
         let mut routes_to: HashMap<(usize, usize), usize> = HashMap::new();
 
         let mut beams = BTreeSet::new();
-        let Some(x) = self.map[0].iter().position(|s| *s == Space::Source) else {
+        let Some(x) = self.map.row(0).iter().position(|s| *s == Space::Source) else {
             panic! {"first line did not have start point"}
         };
         beams.insert(x);
         routes_to.insert((x, 0_usize), 1);
 
-        for (y, row) in self.map.iter().enumerate().skip(1) {
+        for (y, row) in self.map.rows().enumerate().skip(1) {
             let mut new_beams = BTreeSet::new();
             for beam in beams {
                 match row.get(beam) {
@@ -131,7 +138,7 @@ impl Solver for Day07 {
 
         let mut total = 0;
         for ((_, y), n) in routes_to {
-            if y == self.map.len() - 1 {
+            if y == self.map.height() - 1 {
                 total += n;
             }
         }
@@ -142,75 +149,11 @@ impl Solver for Day07 {
 
 #[cfg(test)]
 mod tests {
-    use std::error::Error;
-
     use super::*;
-    use test::Bencher;
-
-    #[test]
-    fn it_works_on_the_example() -> Result<(), Box<dyn Error>> {
-        let input = include_str!("../puzzles/day07/example.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day07::try_create(Box::new(input)).unwrap();
-        assert_eq! {solver.part_one()?, "21"};
-        Ok(())
-    }
-
-    #[test]
-    fn it_works_on_the_other_example() -> Result<(), Box<dyn Error>> {
-        let input = include_str!("../puzzles/day07/example.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day07::try_create(Box::new(input)).unwrap();
-        assert_eq! {solver.part_two()?, "40"};
-        Ok(())
-    }
-
-    #[test]
-    fn it_works_on_my_input() -> Result<(), Box<dyn Error>> {
-        let input = include_str!("../puzzles/day07/joshua.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day07::try_create(Box::new(input)).unwrap();
-        assert_eq! {solver.part_one()?, "1592"};
-        assert_eq! {solver.part_two()?, "17921968177009"};
-        Ok(())
-    }
-
-    #[bench]
-    fn bench_parse(b: &mut Bencher) {
-        b.iter(|| {
-            let input = include_str!("../puzzles/day07/joshua.input")
-                .lines()
-                .map(String::from);
-
-            let _solver = Day07::try_create(Box::new(input)).unwrap();
-        });
-    }
-
-    #[bench]
-    fn bench_one(b: &mut Bencher) {
-        let input = include_str!("../puzzles/day07/joshua.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day07::try_create(Box::new(input)).unwrap();
-
-        b.iter(|| solver.part_one());
-    }
-
-    #[bench]
-    fn bench_two(b: &mut Bencher) {
-        let input = include_str!("../puzzles/day07/joshua.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day07::try_create(Box::new(input)).unwrap();
 
-        b.iter(|| solver.part_two());
+    crate::macros::solver_tests! {
+        Day07, "day07", bench: joshua;
+        example: { part_one: "21", part_two: "40" },
+        joshua: { part_one: "1592", part_two: "17921968177009" },
     }
 }
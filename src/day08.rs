@@ -1,14 +1,17 @@
-extern crate test;
-
-use anyhow::Context;
 use itertools::Itertools;
 use solver::{Solver, SolverToAny};
 use std::cmp::Reverse;
-use std::collections::{BinaryHeap, HashMap};
+use std::collections::BinaryHeap;
+
+use crate::params::Params;
+use crate::parse::{self, Cursor};
+use crate::union_find::UnionFind;
 
 pub struct Day08 {
     points: Vec<Point>,
     diffs: BinaryHeap<Reverse<PointDiff>>,
+    /// How many of the closest pairs part one connects.
+    limit: usize,
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy)]
@@ -26,29 +29,17 @@ struct PointDiff {
     r: Point,
 }
 
-impl TryFrom<(String, usize)> for Point {
-    type Error = anyhow::Error;
-
-    fn try_from((value, id): (String, usize)) -> anyhow::Result<Point> {
-        let [sx, sy, sz] = value.splitn(3, ',').collect::<Vec<_>>()[..] else {
-            anyhow::bail! {"could not split {value}"}
-        };
-
-        let x = sx
-            .parse::<u64>()
-            .context(format! {"could not parse {sx}"})?;
-        let y = sy
-            .parse::<u64>()
-            .context(format! {"could not parse {sy}"})?;
-        let z = sz
-            .parse::<u64>()
-            .context(format! {"could not parse {sz}"})?;
-
-        Ok(Point { x, y, z, id })
+impl Point {
+    /// Reads `x,y,z`; the id is the point's index, filled in afterwards.
+    fn parse(line: &mut Cursor) -> anyhow::Result<Point> {
+        let x = line.number()?;
+        line.expect(",")?;
+        let y = line.number()?;
+        line.expect(",")?;
+        let z = line.number()?;
+        Ok(Point { id: 0, x, y, z })
     }
-}
 
-impl Point {
     fn diff(&self, other: &Self) -> u64 {
         // technically should sqrt, but not necessary for comparisons
         // sqrt(x) < sqrt(y) iff x < y
@@ -65,16 +56,13 @@ impl SolverToAny for Day08 {
 }
 
 impl Day08 {
-    pub fn try_create(input: Box<dyn Iterator<Item = String>>) -> anyhow::Result<Box<dyn Solver>> {
-        let mut points = Vec::new();
-        let mut id = 0_usize;
-        for line in input {
-            if line.is_empty() {
-                continue;
-            }
-            let point = Point::try_from((line, id))?;
-            id += 1;
-            points.push(point);
+    pub fn try_create(
+        input: Box<dyn Iterator<Item = String>>,
+        params: &Params,
+    ) -> anyhow::Result<Box<dyn Solver + Send + Sync>> {
+        let mut points = parse::lines(input, Point::parse)?;
+        for (id, point) in points.iter_mut().enumerate() {
+            point.id = id;
         }
 
         let mut diffs = BinaryHeap::new();
@@ -87,118 +75,30 @@ impl Day08 {
             }
         }
 
-        Ok(Box::new(Day08 { points, diffs }))
+        Ok(Box::new(Day08 {
+            points,
+            diffs,
+            limit: params.get_or("limit", PUZZLE_LIMIT)?,
+        }))
     }
 }
 
-const EXAMPLE_LIMIT: usize = 10;
 const PUZZLE_LIMIT: usize = 1000;
 
-struct DSU {
-    id: usize,
-    parent_id: usize,
-    size: usize,
-}
-
-impl DSU {
-    fn new(id: usize) -> Self {
-        DSU {
-            id,
-            parent_id: id,
-            size: 1,
-        }
-    }
-
-    fn set_parent(&mut self, parent: usize) {
-        self.parent_id = parent;
-    }
-
-    fn set_size(&mut self, size: usize) {
-        self.size = size;
-    }
-}
-
-struct DSF {
-    entries: HashMap<usize, DSU>,
-}
-
-impl DSF {
-    fn new(limit: usize) -> Self {
-        let mut entries = HashMap::new();
-
-        for i in 0_usize..limit {
-            entries.insert(i, DSU::new(i));
-        }
-        DSF { entries }
-    }
-
-    fn merge(&mut self, l: usize, r: usize) {
-        let mut lp = self.parent(l);
-        let mut rp = self.parent(r);
-
-        // Already equal, nothing to do.
-        if lp == rp {
-            return;
-        }
-        let (_sl, _sr) = (self.size(&lp), self.size(&rp));
-
-        if self.size(&lp) > self.size(&rp) {
-            (lp, rp) = (rp, lp);
-        }
-
-        let new_size = self.entries[&lp].size + self.entries[&rp].size;
-        if let Some(n) = self.entries.get_mut(&lp) {
-            n.set_parent(rp)
-        } else {
-            panic! {"could not find {lp}"}
-        };
-        self.entries.get_mut(&rp).unwrap().set_size(new_size);
-    }
-
-    fn parent(&self, mut id: usize) -> usize {
-        let orig_id = id;
-        while let Some(n) = self.entries.get(&id) {
-            if n.parent_id == n.id {
-                return n.id;
-            }
-            id = n.parent_id;
-        }
-        panic! {"could not find parent for {orig_id}"}
-    }
-
-    fn size(&self, id: &usize) -> usize {
-        let parent = self.parent(*id);
-        self.entries[&parent].size
-    }
-}
-
 impl Solver for Day08 {
     fn part_one(&self) -> anyhow::Result<String> {
-        let limit = if self.points.len() < 100 {
-            EXAMPLE_LIMIT
-        } else {
-            PUZZLE_LIMIT
-        };
-        let mut dsf = DSF::new(self.points.len());
+        let mut circuits = UnionFind::new(self.points.len());
         let mut connections = self.diffs.clone();
-        let mut i = 0;
-        while i < limit {
-            if let Some(connection) = connections.pop() {
-                let (lid, rid) = (connection.0.l.id, connection.0.r.id);
-                dsf.merge(lid, rid);
-                i += 1;
-            } else {
-                panic! {"not enough pairs!"}
-            }
+        for _ in 0..self.limit {
+            let Some(Reverse(connection)) = connections.pop() else {
+                anyhow::bail! {"only {} pairs to connect, not {}", self.diffs.len(), self.limit};
+            };
+            circuits.merge(connection.l.id, connection.r.id);
         }
-        let mut parents_by_size = HashMap::new();
-        for point in 0..self.points.len() {
-            let parent = dsf.parent(point);
-            let size = dsf.size(&parent);
-            parents_by_size.insert(parent, size);
-        }
-        Ok(parents_by_size
-            .into_values()
+        Ok(circuits
+            .components()
+            .iter()
+            .map(Vec::len)
             .sorted()
             .rev()
             .take(3)
@@ -207,96 +107,25 @@ impl Solver for Day08 {
     }
 
     fn part_two(&self) -> anyhow::Result<String> {
-        let mut dsf = DSF::new(self.points.len());
+        let mut circuits = UnionFind::new(self.points.len());
         let mut connections = self.diffs.clone();
-        let _i = 0;
-        #[allow(unused_assignments)]
-        let (mut lastl, mut lastr) = (self.points.len(), self.points.len());
-        loop {
-            if let Some(connection) = connections.pop() {
-                let (lid, rid) = (connection.0.l.id, connection.0.r.id);
-                dsf.merge(lid, rid);
-                (lastl, lastr) = (lid, rid);
-                if dsf.size(&rid) == self.points.len() {
-                    break;
-                }
+        while let Some(Reverse(connection)) = connections.pop() {
+            let (l, r) = (connection.l, connection.r);
+            if circuits.merge(l.id, r.id) && circuits.count() == 1 {
+                return Ok((l.x * r.x).to_string());
             }
         }
-        Ok((self.points[lastl].x * self.points[lastr].x).to_string())
+        anyhow::bail! {"connecting every pair still leaves {} circuits", circuits.count()}
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::error::Error;
-
     use super::*;
-    use test::Bencher;
-
-    #[test]
-    fn it_works_on_the_example() -> Result<(), Box<dyn Error>> {
-        let input = include_str!("../puzzles/day08/example.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day08::try_create(Box::new(input)).unwrap();
-        assert_eq! {solver.part_one()?, "40"};
-        Ok(())
-    }
-
-    #[test]
-    fn it_works_on_the_other_example() -> Result<(), Box<dyn Error>> {
-        let input = include_str!("../puzzles/day08/example.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day08::try_create(Box::new(input)).unwrap();
-        assert_eq! {solver.part_two()?, "25272"};
-        Ok(())
-    }
-
-    #[test]
-    fn it_works_on_my_input() -> Result<(), Box<dyn Error>> {
-        let input = include_str!("../puzzles/day08/joshua.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day08::try_create(Box::new(input)).unwrap();
-        assert_eq! {solver.part_one()?, "140008"};
-        assert_eq! {solver.part_two()?, "9253260633"};
-        Ok(())
-    }
-
-    #[bench]
-    fn bench_parse(b: &mut Bencher) {
-        b.iter(|| {
-            let input = include_str!("../puzzles/day08/joshua.input")
-                .lines()
-                .map(String::from);
-
-            let _solver = Day08::try_create(Box::new(input)).unwrap();
-        });
-    }
-
-    #[bench]
-    fn bench_one(b: &mut Bencher) {
-        let input = include_str!("../puzzles/day08/joshua.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day08::try_create(Box::new(input)).unwrap();
-
-        b.iter(|| solver.part_one());
-    }
-
-    #[bench]
-    fn bench_two(b: &mut Bencher) {
-        let input = include_str!("../puzzles/day08/joshua.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day08::try_create(Box::new(input)).unwrap();
 
-        b.iter(|| solver.part_two());
+    crate::macros::solver_tests! {
+        Day08, "day08", bench: joshua;
+        example: { part_one: "40", part_two: "25272" },
+        joshua: { part_one: "140008", part_two: "9253260633" },
     }
 }
@@ -1,7 +1,8 @@
-extern crate test;
-
 use solver::{Solver, SolverToAny};
 
+use crate::params::Params;
+use crate::parse::{self, Cursor};
+
 pub struct Day09 {
     points: Vec<Point>,
 }
@@ -11,18 +12,16 @@ struct Point {
     y: u64,
 }
 
-impl From<String> for Point {
-    fn from(value: String) -> Self {
-        let Some((l, r)) = value.split_once(',') else {
-            panic! {"could not split {value:?}"};
-        };
-        let x = l.parse::<u64>().unwrap();
-        let y = r.parse::<u64>().unwrap();
-        Point { x, y }
+impl Point {
+    fn parse(line: &mut Cursor) -> anyhow::Result<Point> {
+        let x = line.number()?;
+        line.expect(",")?;
+        Ok(Point {
+            x,
+            y: line.number()?,
+        })
     }
-}
 
-impl Point {
     fn rect(&self, other: &Self) -> u64 {
         (self.x.abs_diff(other.x) + 1) * (self.y.abs_diff(other.y) + 1)
     }
@@ -39,12 +38,13 @@ impl SolverToAny for Day09 {
 }
 
 impl Day09 {
-    pub fn try_create(input: Box<dyn Iterator<Item = String>>) -> anyhow::Result<Box<dyn Solver>> {
+    pub fn try_create(
+        input: Box<dyn Iterator<Item = String>>,
+        _params: &Params,
+    ) -> anyhow::Result<Box<dyn Solver + Send + Sync>> {
+        let [points] = parse::sections(input, ["points"])?;
         Ok(Box::new(Day09 {
-            points: input
-                .filter(|s| !s.is_empty())
-                .map(Point::from)
-                .collect::<Vec<_>>(),
+            points: points.parse(Point::parse)?,
         }))
     }
 }
@@ -80,10 +80,6 @@ impl Solver for Day09 {
             } else {
                 vertical.push((&self.points[i], &self.points[j]));
             }
-        }
-        let mut largest_area = 0;
-        for point in &self.points {
-
         }
         Err(anyhow::anyhow! {"Not Implemented yet"})
     }
@@ -91,75 +87,11 @@ impl Solver for Day09 {
 
 #[cfg(test)]
 mod tests {
-    use std::error::Error;
-
     use super::*;
-    use test::Bencher;
-
-    #[test]
-    fn it_works_on_the_example() -> Result<(), Box<dyn Error>> {
-        let input = include_str!("../puzzles/day09/example.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day09::try_create(Box::new(input)).unwrap();
-        assert_eq! {solver.part_one()?, "50"};
-        Ok(())
-    }
-
-    #[test]
-    fn it_works_on_the_other_example() -> Result<(), Box<dyn Error>> {
-        let input = include_str!("../puzzles/day09/example.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day09::try_create(Box::new(input)).unwrap();
-        assert! {solver.part_two().is_err()};
-        Ok(())
-    }
-
-    #[test]
-    fn it_works_on_my_input() -> Result<(), Box<dyn Error>> {
-        let input = include_str!("../puzzles/day09/joshua.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day09::try_create(Box::new(input)).unwrap();
-        assert_eq! {solver.part_one()?, "4755429952"};
-        assert! {solver.part_two().is_err()};
-        Ok(())
-    }
-
-    #[bench]
-    fn bench_parse(b: &mut Bencher) {
-        b.iter(|| {
-            let input = include_str!("../puzzles/day09/joshua.input")
-                .lines()
-                .map(String::from);
-
-            let _solver = Day09::try_create(Box::new(input)).unwrap();
-        });
-    }
-
-    #[bench]
-    fn bench_one(b: &mut Bencher) {
-        let input = include_str!("../puzzles/day09/joshua.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day09::try_create(Box::new(input)).unwrap();
-
-        b.iter(|| solver.part_one());
-    }
-
-    #[bench]
-    fn bench_two(b: &mut Bencher) {
-        let input = include_str!("../puzzles/day09/joshua.input")
-            .lines()
-            .map(String::from);
-
-        let solver = Day09::try_create(Box::new(input)).unwrap();
 
-        b.iter(|| solver.part_two());
+    crate::macros::solver_tests! {
+        Day09, "day09", bench: joshua;
+        example: { part_one: "50", part_two: Err },
+        joshua: { part_one: "4755429952", part_two: Err },
     }
 }
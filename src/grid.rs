@@ -0,0 +1,224 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+use crate::parse::Cursor;
+
+/// A position in a grid, as `(x, y)` with `y` counting rows down from the top.
+pub type Point = (usize, usize);
+
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular map of cells, such as a puzzle input drawn with one
+/// character per cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Reads one row per line, converting each character into a cell. Rows of
+    /// different lengths are an error, though blank lines at the end are
+    /// ignored.
+    pub fn parse<S: AsRef<str>>(lines: impl IntoIterator<Item = S>) -> anyhow::Result<Self>
+    where
+        T: TryFrom<char>,
+        anyhow::Error: From<T::Error>,
+    {
+        let mut lines = lines.into_iter().collect::<Vec<_>>();
+        while lines.last().is_some_and(|line| line.as_ref().is_empty()) {
+            lines.pop();
+        }
+        let mut width = None;
+        let mut cells = Vec::new();
+        for (y, line) in lines.iter().enumerate() {
+            let mut row = Cursor::new(y + 1, line.as_ref());
+            let start = cells.len();
+            while let Some(c) = row.peek() {
+                let cell = T::try_from(c).map_err(|e| row.error(anyhow::Error::from(e)))?;
+                cells.push(cell);
+                row.char()?;
+            }
+            let len = cells.len() - start;
+            match width {
+                None => width = Some(len),
+                Some(width) if width != len => {
+                    return Err(row.error(format! {
+                        "the row is {len} wide, but the rows above are {width} wide"
+                    }));
+                }
+                Some(_) => {}
+            }
+        }
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            cells,
+        })
+    }
+
+    #[allow(dead_code)] // No day needs it yet, unlike `height`.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len().checked_div(self.width).unwrap_or(0)
+    }
+
+    pub fn get(&self, (x, y): Point) -> Option<&T> {
+        (x < self.width).then(|| self.cells.get(y * self.width + x))?
+    }
+
+    pub fn get_mut(&mut self, (x, y): Point) -> Option<&mut T> {
+        (x < self.width).then(|| self.cells.get_mut(y * self.width + x))?
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    #[allow(dead_code)] // The counterpart to `row`, which no day needs yet.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert! {x < self.width, "column {x} is outside a grid {} wide", self.width};
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    /// The points above, left, right and below `point` that are in the grid.
+    #[allow(dead_code)] // Mazes tend to need it, but no day has been one yet.
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + use<'_, T> {
+        self.offsets(point, &ORTHOGONAL)
+    }
+
+    /// The up to eight points around `point`, diagonals included.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + use<'_, T> {
+        self.offsets(point, &SURROUNDING)
+    }
+
+    fn offsets(
+        &self,
+        (x, y): Point,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Point> + use<'_, T> {
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let x = x.checked_add_signed(dx).filter(|x| *x < self.width)?;
+            let y = y.checked_add_signed(dy).filter(|y| *y < self.height())?;
+            Some((x, y))
+        })
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic! {"{point:?} is outside the grid"})
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic! {"{point:?} is outside the grid"})
+    }
+}
+
+/// Draws the grid back out one row per line, as it was parsed.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write! {f, "{cell}"}?;
+            }
+            writeln! {f}?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Digit(u8);
+
+    impl TryFrom<char> for Digit {
+        type Error = anyhow::Error;
+
+        fn try_from(c: char) -> anyhow::Result<Self> {
+            match c.to_digit(10) {
+                Some(d) => Ok(Digit(d as u8)),
+                None => anyhow::bail! {"{c:?} is not a digit"},
+            }
+        }
+    }
+
+    impl Display for Digit {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write! {f, "{}", self.0}
+        }
+    }
+
+    #[test]
+    fn it_parses_and_draws() -> anyhow::Result<()> {
+        let grid = Grid::<Digit>::parse(["123", "456"])?;
+        assert_eq! {(grid.width(), grid.height()), (3, 2)};
+        assert_eq! {grid[(2, 1)], Digit(6)};
+        assert_eq! {grid.get((3, 0)), None};
+        assert_eq! {grid.get((0, 2)), None};
+        assert_eq! {grid.row(1), [4, 5, 6].map(Digit)};
+        assert_eq! {grid.column(1).copied().collect::<Vec<_>>(), [2, 5].map(Digit)};
+        assert_eq! {grid.to_string(), "123\n456\n"};
+
+        assert_eq! {Grid::<Digit>::parse(["12", "34", "", ""])?.height(), 2};
+        let empty = Grid::<Digit>::parse([] as [&str; 0])?;
+        assert_eq! {(empty.width(), empty.height(), empty.rows().count()), (0, 0, 0)};
+        Ok(())
+    }
+
+    #[test]
+    fn it_rejects_bad_rows() {
+        let ragged = Grid::<Digit>::parse(["123", "45", "678"]).unwrap_err();
+        assert! {Grid::<Digit>::parse(["12", "", "34"]).is_err()};
+        assert_eq! {
+            ragged.to_string(),
+            "line 2, column 3: the row is 2 wide, but the rows above are 3 wide"
+        };
+        let bad = Grid::<Digit>::parse(["123", "4x6"]).unwrap_err();
+        assert_eq! {bad.to_string(), "line 2, column 2: 'x' is not a digit"};
+    }
+
+    #[test]
+    fn it_finds_neighbours_in_bounds() -> anyhow::Result<()> {
+        let grid = Grid::<Digit>::parse(["123", "456", "789"])?;
+        assert_eq! {grid.neighbours4((0, 0)).collect::<Vec<_>>(), [(1, 0), (0, 1)]};
+        assert_eq! {grid.neighbours4((1, 1)).count(), 4};
+        assert_eq! {grid.neighbours8((2, 2)).collect::<Vec<_>>(), [(1, 1), (2, 1), (1, 2)]};
+        assert_eq! {grid.neighbours8((1, 1)).count(), 8};
+        Ok(())
+    }
+}
@@ -0,0 +1,291 @@
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+
+use anyhow::Context;
+
+const USER_AGENT: &str = "github.com/JoBoCl/aoc2025_rs";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+}
+
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: Method,
+    pub url: String,
+    pub session: String,
+    /// Sent as an `application/x-www-form-urlencoded` body.
+    pub form: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+pub trait Client {
+    fn send(&self, request: &Request) -> anyhow::Result<Response>;
+}
+
+/// Picks `Curl` for the real site and `PlainHttp` for `http://` stand-ins.
+pub fn client_for(base_url: &str) -> Box<dyn Client> {
+    if base_url.starts_with("http://") {
+        Box::new(PlainHttp)
+    } else {
+        Box::new(Curl)
+    }
+}
+
+pub fn form_encode(form: &[(String, String)]) -> String {
+    let encode = |s: &str| {
+        s.bytes()
+            .map(|b| match b {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                    (b as char).to_string()
+                }
+                b' ' => "+".to_string(),
+                b => format! {"%{b:02X}"},
+            })
+            .collect::<String>()
+    };
+    form.iter()
+        .map(|(k, v)| format! {"{}={}", encode(k), encode(v)})
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Shells out to `curl`, which handles TLS for us.
+pub struct Curl;
+
+impl Client for Curl {
+    fn send(&self, request: &Request) -> anyhow::Result<Response> {
+        let mut command = Command::new("curl");
+        command
+            .args(["--silent", "--show-error", "--location"])
+            .args(["--user-agent", USER_AGENT])
+            // The cookie is read from a config on stdin, as anyone can see the
+            // command line.
+            .args(["--config", "-"])
+            // The status code goes on a line of its own after the body.
+            .args(["--write-out", "\n%{http_code}"]);
+        if request.method == Method::Post {
+            command.args(["--data", &form_encode(&request.form)]);
+        }
+        let mut child = command
+            .arg(&request.url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("could not run curl")?;
+        let config = format! {
+            "cookie = \"session={}\"\n",
+            request.session.replace('\\', "\\\\").replace('"', "\\\"")
+        };
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(config.as_bytes())
+            .context("could not pass the session cookie to curl")?;
+        let output = child.wait_with_output().context("could not run curl")?;
+        if !output.status.success() {
+            anyhow::bail! {
+                "curl failed for {}: {}",
+                request.url,
+                String::from_utf8_lossy(&output.stderr).trim()
+            };
+        }
+
+        let stdout = String::from_utf8(output.stdout).context("response was not UTF-8")?;
+        let Some((body, status)) = stdout.rsplit_once('\n') else {
+            anyhow::bail! {"curl did not report a status for {}", request.url};
+        };
+        Ok(Response {
+            status: status
+                .trim()
+                .parse()
+                .context("curl reported a bad status")?,
+            body: body.to_string(),
+        })
+    }
+}
+
+/// A bare-bones HTTP/1.1 client over `TcpStream`, for talking to a local
+/// stand-in server in tests. It doesn't speak TLS or chunked encoding.
+pub struct PlainHttp;
+
+impl Client for PlainHttp {
+    fn send(&self, request: &Request) -> anyhow::Result<Response> {
+        let Some(rest) = request.url.strip_prefix("http://") else {
+            anyhow::bail! {"{} is not an http:// URL", request.url};
+        };
+        let (host, path) = match rest.find('/') {
+            Some(idx) => rest.split_at(idx),
+            None => (rest, "/"),
+        };
+
+        let (method, body) = match request.method {
+            Method::Get => ("GET", String::new()),
+            Method::Post => ("POST", form_encode(&request.form)),
+        };
+        let mut message = format! {
+            "{method} {path} HTTP/1.1\r\nHost: {host}\r\nUser-Agent: {USER_AGENT}\r\n\
+             Cookie: session={}\r\nConnection: close\r\n",
+            request.session
+        };
+        if request.method == Method::Post {
+            message += "Content-Type: application/x-www-form-urlencoded\r\n";
+            message += &format! {"Content-Length: {}\r\n", body.len()};
+        }
+        message += "\r\n";
+        message += &body;
+
+        let mut stream =
+            TcpStream::connect(host).with_context(|| format! {"could not connect to {host}"})?;
+        stream.write_all(message.as_bytes())?;
+        let mut raw = String::new();
+        stream
+            .read_to_string(&mut raw)
+            .with_context(|| format! {"could not read the response from {host}"})?;
+
+        let Some((head, body)) = raw.split_once("\r\n\r\n") else {
+            anyhow::bail! {"malformed response from {host}"};
+        };
+        let status = head
+            .split_whitespace()
+            .nth(1)
+            .and_then(|s| s.parse().ok())
+            .with_context(|| format! {"malformed status line from {host}"})?;
+        Ok(Response {
+            status,
+            body: body.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+pub mod testing {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread::JoinHandle;
+
+    /// A local stand-in for the site: answers each connection with the next
+    /// canned `(status, body)` and keeps the raw requests it was sent.
+    pub struct Server {
+        pub url: String,
+        pub requests: Arc<Mutex<Vec<String>>>,
+        handle: JoinHandle<()>,
+    }
+
+    impl Server {
+        pub fn start(responses: Vec<(u16, &'static str)>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format! {"http://{}", listener.local_addr().unwrap()};
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let seen = requests.clone();
+            let handle = std::thread::spawn(move || {
+                for (status, body) in responses {
+                    let (stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream);
+                    let mut request = String::new();
+                    let mut length = 0;
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        if let Some(l) = line.strip_prefix("Content-Length: ") {
+                            length = l.trim().parse().unwrap();
+                        }
+                        request += &line;
+                        if line == "\r\n" {
+                            break;
+                        }
+                    }
+                    let mut body_bytes = vec![0; length];
+                    reader.read_exact(&mut body_bytes).unwrap();
+                    request += &String::from_utf8(body_bytes).unwrap();
+                    seen.lock().unwrap().push(request);
+
+                    let mut stream = reader.into_inner();
+                    write! {
+                        stream,
+                        "HTTP/1.1 {status} X\r\nContent-Length: {}\r\n\r\n{body}",
+                        body.len()
+                    }
+                    .unwrap();
+                }
+            });
+            Server {
+                url,
+                requests,
+                handle,
+            }
+        }
+
+        /// Waits for every canned response to have been used.
+        pub fn finish(self) -> Vec<String> {
+            self.handle.join().unwrap();
+            self.requests.lock().unwrap().clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_encodes_forms() {
+        let form = [("level", "1"), ("answer", "a b&c")].map(|(k, v)| (k.into(), v.into()));
+        assert_eq! {form_encode(&form), "level=1&answer=a+b%26c"};
+    }
+
+    #[test]
+    fn it_talks_plain_http() -> anyhow::Result<()> {
+        let server = testing::Server::start(vec![(200, "hello\n"), (404, "nope")]);
+        let mut request = Request {
+            method: Method::Get,
+            url: format! {"{}/2025/day/1/input", server.url},
+            session: "abc".to_string(),
+            form: Vec::new(),
+        };
+        assert_eq! {
+            PlainHttp.send(&request)?,
+            Response { status: 200, body: "hello\n".to_string() }
+        };
+        request.method = Method::Post;
+        request.form = vec![("level".into(), "2".into())];
+        assert_eq! {PlainHttp.send(&request)?.status, 404};
+
+        let requests = server.finish();
+        assert! {requests[0].starts_with("GET /2025/day/1/input HTTP/1.1\r\n")};
+        assert! {requests[0].contains("Cookie: session=abc\r\n")};
+        assert! {requests[1].starts_with("POST /2025/day/1/input HTTP/1.1\r\n")};
+        assert! {requests[1].ends_with("\r\n\r\nlevel=2")};
+        Ok(())
+    }
+
+    #[test]
+    fn it_gives_curl_the_cookie() -> anyhow::Result<()> {
+        let server = testing::Server::start(vec![(200, "hello\n")]);
+        let request = Request {
+            method: Method::Post,
+            url: format! {"{}/2025/day/1/answer", server.url},
+            session: "abc".to_string(),
+            form: vec![("answer".into(), "42".into())],
+        };
+        assert_eq! {
+            Curl.send(&request)?,
+            Response { status: 200, body: "hello\n".to_string() }
+        };
+        let requests = server.finish();
+        assert! {requests[0].contains("Cookie: session=abc\r\n"), "{}", requests[0]};
+        assert! {requests[0].ends_with("answer=42")};
+        Ok(())
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+use crate::parse::Cursor;
+
+/// A set of `u64`s stored as the inclusive ranges it covers. Overlapping and
+/// touching ranges are merged as they are inserted, so the ranges are always
+/// disjoint, with a gap between each, and iterate in order.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct IntervalSet {
+    /// Each range's end, by its start.
+    ranges: BTreeMap<u64, u64>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        IntervalSet::default()
+    }
+
+    /// Adds `range`, merging it with any ranges it overlaps or touches. An
+    /// empty range, which ends before it starts, adds nothing.
+    pub fn insert(&mut self, range: RangeInclusive<u64>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        if let Some((&before, &before_end)) = self.ranges.range(..=start).next_back()
+            && before_end.saturating_add(1) >= start
+        {
+            start = before;
+        }
+        let merged = self
+            .ranges
+            .range(start..=end.saturating_add(1))
+            .map(|(&start, &end)| (start, end))
+            .collect::<Vec<_>>();
+        for (merged_start, merged_end) in merged {
+            self.ranges.remove(&merged_start);
+            end = end.max(merged_end);
+        }
+        self.ranges.insert(start, end);
+    }
+
+    pub fn contains(&self, value: u64) -> bool {
+        self.ranges
+            .range(..=value)
+            .next_back()
+            .is_some_and(|(_, &end)| value <= end)
+    }
+
+    /// How many values the set holds, which is one too many for a `u64`
+    /// when it holds them all.
+    pub fn covered(&self) -> u128 {
+        self.ranges
+            .iter()
+            .map(|(start, end)| u128::from(end - start) + 1)
+            .sum()
+    }
+
+    /// The disjoint ranges, in order.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<u64>> + '_ {
+        self.ranges.iter().map(|(&start, &end)| start..=end)
+    }
+
+    #[allow(dead_code)] // Set operations for range puzzles to come.
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut union = self.clone();
+        union.extend(other.iter());
+        union
+    }
+
+    #[allow(dead_code)] // Neither Day02 nor Day05 combines sets.
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intersection = IntervalSet::new();
+        let mut left = self.ranges.iter().peekable();
+        let mut right = other.ranges.iter().peekable();
+        while let (Some(&(&l_start, &l_end)), Some(&(&r_start, &r_end))) =
+            (left.peek(), right.peek())
+        {
+            let (start, end) = (l_start.max(r_start), l_end.min(r_end));
+            if start <= end {
+                // Pieces of disjoint ranges can't touch, so need no merging.
+                intersection.ranges.insert(start, end);
+            }
+            if l_end < r_end {
+                left.next();
+            } else {
+                right.next();
+            }
+        }
+        intersection
+    }
+
+    /// The values in `self` that are not in `other`.
+    #[allow(dead_code)] // Like `intersection`, kept for later days.
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut difference = IntervalSet::new();
+        let mut others = other.ranges.iter().peekable();
+        for (&start, &end) in &self.ranges {
+            let mut from = Some(start);
+            while let (Some(start), Some(&(&o_start, &o_end))) = (from, others.peek()) {
+                if o_end < start {
+                    others.next();
+                    continue;
+                }
+                if o_start > end {
+                    break;
+                }
+                if o_start > start {
+                    difference.ranges.insert(start, o_start - 1);
+                }
+                from = o_end.checked_add(1).filter(|&next| next <= end);
+                if o_end <= end {
+                    others.next();
+                }
+            }
+            if let Some(start) = from {
+                difference.ranges.insert(start, end);
+            }
+        }
+        difference
+    }
+}
+
+impl Extend<RangeInclusive<u64>> for IntervalSet {
+    fn extend<I: IntoIterator<Item = RangeInclusive<u64>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl FromIterator<RangeInclusive<u64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<u64>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+/// Reads a range written as `start-end`, both ends included.
+pub fn parse_range(line: &mut Cursor) -> anyhow::Result<RangeInclusive<u64>> {
+    let start = line.number()?;
+    line.expect("-")?;
+    Ok(start..=line.number()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[RangeInclusive<u64>]) -> IntervalSet {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn it_merges_on_insert() {
+        let mut ranges = set(&[10..=14, 3..=5, 16..=20, 12..=18]);
+        assert_eq! {ranges.iter().collect::<Vec<_>>(), [3..=5, 10..=20]};
+        ranges.insert(6..=9);
+        assert_eq! {ranges.iter().collect::<Vec<_>>(), [3..=20]};
+        let (start, end) = (30, 29);
+        ranges.insert(start..=end);
+        ranges.insert(u64::MAX - 1..=u64::MAX);
+        ranges.insert(u64::MAX..=u64::MAX);
+        assert_eq! {ranges.iter().collect::<Vec<_>>(), [3..=20, u64::MAX - 1..=u64::MAX]};
+    }
+
+    #[test]
+    fn it_answers_queries() {
+        let ranges = set(&[3..=5, 10..=14]);
+        assert! {ranges.contains(3) && ranges.contains(5) && ranges.contains(12)};
+        assert! {!ranges.contains(2) && !ranges.contains(6) && !ranges.contains(15)};
+        assert_eq! {ranges.covered(), 8};
+        assert_eq! {set(&[0..=u64::MAX]).covered(), 1 << 64};
+    }
+
+    #[test]
+    fn it_combines_sets() {
+        let a = set(&[1..=10, 20..=30, 40..=40]);
+        let b = set(&[5..=22, 28..=35, 50..=60]);
+        assert_eq! {a.union(&b), set(&[1..=35, 40..=40, 50..=60])};
+        assert_eq! {a.intersection(&b), set(&[5..=10, 20..=22, 28..=30])};
+        assert_eq! {a.difference(&b), set(&[1..=4, 23..=27, 40..=40])};
+        assert_eq! {b.difference(&a), set(&[11..=19, 31..=35, 50..=60])};
+        assert_eq! {a.difference(&a), IntervalSet::new()};
+        assert_eq! {set(&[0..=u64::MAX]).difference(&set(&[5..=u64::MAX])), set(&[0..=4])};
+    }
+
+    #[test]
+    fn it_parses_ranges() -> anyhow::Result<()> {
+        let range = |s| parse_range(&mut Cursor::new(1, s));
+        assert_eq! {range("11-22")?, 11..=22};
+        assert! {range("11").is_err()};
+        assert! {range("11-x").is_err()};
+        Ok(())
+    }
+}
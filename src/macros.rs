@@ -0,0 +1,104 @@
+/// Generates a day's tests and benches from a table of expected answers, to go
+/// inside its `mod tests`. Each row names an input in `puzzles/<dir>/` and
+/// gives a module of tests for the parts it lists, so a numbered example that
+/// only goes with one part lists just that part. Parameters for `try_create`
+/// come from the input's `.params` file, as when running it, and can be added
+/// to or overridden in brackets after the input. An answer of `Err` expects
+/// the part to fail. With the `nightly` feature, benches time parsing and each
+/// part on the `bench` input.
+///
+/// ```ignore
+/// crate::macros::solver_tests! {
+///     Day09, "day09", bench: joshua;
+///     example1(limit = 10): { part_one: "50" },
+///     example2: { part_two: Err },
+///     joshua: { part_one: "4755429952", part_two: Err },
+/// }
+/// ```
+macro_rules! solver_tests {
+    (
+        $day:ident, $dir:literal, bench: $bench:ident;
+        $(
+            $input:ident $(($($name:ident = $value:literal),* $(,)?))?:
+            { $($part:ident: $expected:tt),* $(,)? }
+        ),* $(,)?
+    ) => {
+        $(
+            mod $input {
+                use super::*;
+
+                pub(super) fn params() -> anyhow::Result<$crate::params::Params> {
+                    let file = $crate::params::Params::load(std::path::Path::new(concat!(
+                        env!("CARGO_MANIFEST_DIR"),
+                        "/puzzles/", $dir, "/", stringify!($input), ".params"
+                    )))?;
+                    let table = Vec::<(String, String)>::from([
+                        $($((stringify!($name).to_string(), $value.to_string())),*)?
+                    ]);
+                    Ok(file.merge(&table.into_iter().collect()))
+                }
+
+                pub(super) fn solver() -> anyhow::Result<Box<dyn solver::Solver + Send + Sync>> {
+                    $crate::macros::solver_tests! {@create $day, $dir, $input, &params()?}
+                }
+
+                $(
+                    #[test]
+                    fn $part() -> anyhow::Result<()> {
+                        let answer = solver()?.$part();
+                        $crate::macros::solver_tests! {@check answer, $expected}
+                        Ok(())
+                    }
+                )*
+            }
+        )*
+
+        #[cfg(feature = "nightly")]
+        mod bench {
+            use super::*;
+            use test::Bencher;
+
+            #[bench]
+            fn parse(b: &mut Bencher) {
+                let params = super::$bench::params().unwrap();
+                b.iter(|| -> anyhow::Result<Box<dyn solver::Solver + Send + Sync>> {
+                    $crate::macros::solver_tests! {@create $day, $dir, $bench, &params}
+                });
+            }
+
+            #[bench]
+            fn part_one(b: &mut Bencher) {
+                let solver = super::$bench::solver().unwrap();
+                b.iter(|| solver.part_one());
+            }
+
+            #[bench]
+            fn part_two(b: &mut Bencher) {
+                let solver = super::$bench::solver().unwrap();
+                b.iter(|| solver.part_two());
+            }
+        }
+    };
+
+    (@create $day:ident, $dir:literal, $input:ident, $params:expr) => {
+        $crate::runner::construct(
+            $day::try_create,
+            Box::new(
+                include_str!(concat!("../puzzles/", $dir, "/", stringify!($input), ".input"))
+                    .lines()
+                    .map(String::from),
+            ),
+            $params,
+        )
+    };
+
+    (@check $answer:ident, Err) => {
+        assert! {$answer.is_err(), "expected an error, got {:?}", $answer.ok()};
+    };
+
+    (@check $answer:ident, $expected:literal) => {
+        assert_eq! {$answer?, $expected};
+    };
+}
+
+pub(crate) use solver_tests;
@@ -1,9 +1,9 @@
-#![feature(test)]
-#![feature(trait_alias)]
-#![feature(const_convert)]
-#![feature(const_trait_impl)]
+#![cfg_attr(feature = "nightly", feature(test))]
 
-use clap::Parser;
+#[cfg(feature = "nightly")]
+extern crate test;
+
+use clap::{Parser, Subcommand};
 
 // BEGIN_MOD_LIST
 mod day00;
@@ -17,42 +17,594 @@ mod day07;
 mod day08;
 mod day09;
 // END_MOD_LIST
+mod answers;
+mod aoc;
+mod bench;
+mod cpu;
+mod grid;
+mod http;
+mod interval;
+#[cfg(test)]
+mod macros;
+mod memory;
+mod params;
+mod parse;
+mod pool;
+mod registry;
+mod repl;
+mod report;
+mod runner;
+mod scaffold;
+mod table;
+mod union_find;
+mod watch;
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use solver::Solver;
+use answers::{Check, Expected, Log, Verdict};
+use params::Params;
+use report::Format;
+use runner::{DayResult, Part, Source};
 
 #[derive(Debug, Parser)]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    run: RunArgs,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Time parse, part one and part two over repeated runs
+    Bench(BenchArgs),
+    /// Create the module, registration and puzzle directory for a new day
+    NewDay(NewDayArgs),
+    /// Download puzzle inputs that aren't already in `puzzles/`
+    Fetch(FetchArgs),
+    /// Compute one part's answer for `--user` and submit it
+    Submit(SubmitArgs),
+    /// Re-run a day's inputs and examples whenever its puzzle files change
+    Watch(WatchArgs),
+    /// Parse a day's input once, then run parts, swap inputs and print the
+    /// parsed input from a prompt
+    Repl(ReplArgs),
+}
+
+#[derive(Debug, clap::Args)]
+struct PartArgs {
+    /// Which part(s) to run
+    #[arg(short, long, default_value = "both")]
+    part: Part,
+}
+
+#[derive(Debug, clap::Args)]
+struct TimeoutArgs {
+    /// Give up on a parse or part after this many seconds, reporting it as
+    /// timed out
+    #[arg(long, value_parser = seconds)]
+    timeout: Option<Duration>,
+}
+
+#[derive(Debug, clap::Args)]
+struct ParamArgs {
+    /// A `name=value` parameter for the solvers, overriding any `.params`
+    /// file next to the input
+    #[arg(long = "param", value_name = "NAME=VALUE", value_parser = params::parse_pair)]
+    params: Vec<(String, String)>,
+}
+
+impl ParamArgs {
+    fn overrides(&self) -> Params {
+        Params::from_iter(self.params.clone())
+    }
+}
+
+#[derive(Debug, clap::Args)]
+struct RunArgs {
+    #[arg(short, long, required_unless_present_any = ["all", "list"])]
+    day: Option<usize>,
+    /// Puzzle input file, or `-` to read it from stdin
+    #[arg(short, long, required_unless_present_any = ["all", "list", "text", "examples"])]
+    input: Option<String>,
+    /// Puzzle input given inline instead of as a file
+    #[arg(short, long, conflicts_with = "input")]
+    text: Option<String>,
+    /// Run every day with an input for `--user` and print a summary table
+    #[arg(short, long, conflicts_with_all = ["day", "input", "text"])]
+    all: bool,
+    /// Run the day's (or with `--all`, every day's) `example*.input` files
+    /// instead, checking each part that its `.output` has an answer for
+    #[arg(short, long, conflicts_with_all = ["input", "text", "user"])]
+    examples: bool,
+    /// List the registered days
+    #[arg(short, long, conflicts_with_all = ["day", "input", "text", "all", "examples"])]
+    list: bool,
+    /// Only run `puzzles/dayNN/<user>.input` with `--all`, rather than every
+    /// user's input
+    #[arg(short, long)]
+    user: Option<String>,
+    /// Save answers for parts without a known answer to the `.output` file
+    #[arg(short, long)]
+    record: bool,
+    #[command(flatten)]
+    parts: PartArgs,
+    /// How to print results; defaults to a table for `--all` and bare answers
+    /// for a single day
+    #[arg(short, long)]
+    format: Option<Format>,
+    /// Threads to run inputs on with `--all` or `--examples`; defaults to one
+    /// per CPU
+    #[arg(short, long)]
+    jobs: Option<usize>,
+    #[command(flatten)]
+    limit: TimeoutArgs,
+    #[command(flatten)]
+    params: ParamArgs,
+}
+
+#[derive(Debug, clap::Args)]
+struct BenchArgs {
+    #[arg(short, long, required_unless_present = "all")]
+    day: Option<usize>,
+    /// Benchmark every day with an input for `--user`
+    #[arg(short, long, conflicts_with = "day")]
+    all: bool,
+    /// Whose `puzzles/dayNN/<user>.input` to benchmark with
+    #[arg(short, long, default_value = "joshua")]
+    user: String,
+    /// Timed runs of each phase
+    #[arg(short = 'n', long, default_value_t = 25)]
+    runs: usize,
+    /// Stop timing a phase once it has taken this many seconds
+    #[arg(short, long, default_value = "5", value_parser = seconds)]
+    budget: Duration,
+    #[command(flatten)]
+    parts: PartArgs,
+    #[command(flatten)]
+    params: ParamArgs,
+}
+
+#[derive(Debug, clap::Args)]
+struct NewDayArgs {
+    #[arg(short, long)]
+    day: usize,
+    /// The puzzle's title, shown by `--list`
+    #[arg(short, long, default_value = "")]
+    title: String,
+}
+
+#[derive(Debug, clap::Args)]
+struct SiteArgs {
+    /// File holding the session cookie, unless AOC_SESSION is set
+    #[arg(short, long, default_value = ".cookie")]
+    cookie: PathBuf,
+    #[arg(long, default_value = aoc::DEFAULT_URL)]
+    base_url: String,
+}
+
+impl SiteArgs {
+    fn connect(&self, interval: Duration) -> anyhow::Result<aoc::Site> {
+        Ok(aoc::Site::new(
+            http::client_for(&self.base_url),
+            &self.base_url,
+            aoc::session(&self.cookie)?,
+            interval,
+        ))
+    }
+}
+
+#[derive(Debug, clap::Args)]
+struct FetchArgs {
+    #[arg(short, long, required = true, num_args = 1..)]
+    day: Vec<usize>,
+    /// Save inputs as `puzzles/dayNN/<user>.input`
+    #[arg(short, long, default_value = "joshua")]
+    user: String,
+    /// Seconds to wait between requests
+    #[arg(long, default_value = "5", value_parser = seconds)]
+    interval: Duration,
+    #[command(flatten)]
+    site: SiteArgs,
+}
+
+#[derive(Debug, clap::Args)]
+struct SubmitArgs {
+    #[arg(short, long)]
+    day: usize,
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+    /// Solve `puzzles/dayNN/<user>.input`, and record the verdict next to it
+    #[arg(short, long, default_value = "joshua")]
+    user: String,
+    /// Submit this answer instead of computing it
+    #[arg(short, long)]
+    answer: Option<String>,
+    #[command(flatten)]
+    site: SiteArgs,
+}
+
+#[derive(Debug, clap::Args)]
+struct WatchArgs {
+    /// Watch `puzzles/dayNN/`; changes to `src/dayNN.rs` need a rebuild, so
+    /// run this under `cargo watch` to pick those up too
     #[arg(short, long)]
     day: usize,
+    #[command(flatten)]
+    parts: PartArgs,
+    /// Seconds between checks for changes
+    #[arg(long, default_value = "0.5", value_parser = seconds)]
+    interval: Duration,
+    #[command(flatten)]
+    limit: TimeoutArgs,
+    #[command(flatten)]
+    params: ParamArgs,
+}
+
+#[derive(Debug, clap::Args)]
+struct ReplArgs {
     #[arg(short, long)]
+    day: usize,
+    /// An input's name in `puzzles/dayNN/`, like `example`, or a path to one
+    #[arg(short, long, default_value = "joshua")]
     input: String,
+    #[command(flatten)]
+    limit: TimeoutArgs,
+    #[command(flatten)]
+    params: ParamArgs,
+}
+
+/// Reads a number of seconds, which can have a fraction but can't be negative.
+fn seconds(arg: &str) -> Result<Duration, String> {
+    let seconds = arg
+        .parse::<f64>()
+        .map_err(|e| format! {"{arg:?} is not a number of seconds: {e}"})?;
+    Duration::try_from_secs_f64(seconds)
+        .map_err(|_| format! {"{arg:?} is not a non-negative number of seconds"})
+}
+
+fn list() {
+    for entry in registry::SOLVERS {
+        println! {"{:>2}  {}  {}", entry.day, entry.name(), entry.title};
+    }
+}
+
+/// Checks `result` against the `.output` file next to its input, first
+/// recording any new answers into it if asked to, and warns about answers that
+/// the submission log already proves wrong.
+fn check(result: &mut DayResult, path: &Path, record: bool) -> anyhow::Result<()> {
+    let log = Log::load(&path.with_extension("log"))?;
+    for part in &result.parts {
+        if let Ok(answer) = &part.answer
+            && let Some(reason) = log.disproves(part.part, answer)
+        {
+            eprintln! {"warning: day {:02} part {}: {reason}", result.day, part.part};
+        }
+    }
+
+    let mut expected = Expected::load(path)?;
+    if record {
+        let mut changed = false;
+        for part in &result.parts {
+            if let Ok(answer) = &part.answer
+                && log.disproves(part.part, answer).is_none()
+            {
+                match expected.record(part.part, answer) {
+                    Ok(new) => changed |= new,
+                    Err(e) => eprintln! {"day {:02}: {e}", result.day},
+                }
+            }
+        }
+        if changed {
+            expected.save(path)?;
+        }
+    }
+    result.check(&expected);
+    Ok(())
+}
+
+/// A job for each of the day's personal inputs, or its examples. An example
+/// only runs the parts its `.output` has answers for.
+fn day_jobs(
+    entry: &registry::Entry,
+    examples: bool,
+    part: Part,
+    params: &Params,
+) -> anyhow::Result<Vec<runner::Job>> {
+    let sources = if examples {
+        runner::example_inputs(entry.day)?
+    } else {
+        runner::user_inputs(entry.day)?
+    };
+    let mut jobs = Vec::new();
+    for source in sources {
+        let mut part = part;
+        if examples && let Some(output) = source.output() {
+            let Some(known) = part.restrict(&Expected::load(&output)?) else {
+                continue;
+            };
+            part = known;
+        }
+        jobs.push(runner::Job {
+            day: entry.day,
+            part,
+            params: source.params(params)?,
+            source,
+            create: entry.try_create,
+        });
+    }
+    Ok(jobs)
+}
+
+/// Runs every input of the selected days: each day with `--all`, and the
+/// personal inputs unless `--examples` picks the examples.
+fn run_all(flags: &RunArgs) -> anyhow::Result<usize> {
+    let entries = match flags.day {
+        Some(day) => vec![registry::find(day)?],
+        None => registry::puzzles().collect(),
+    };
+    let params = flags.params.overrides();
+    let mut jobs = Vec::new();
+    for entry in entries {
+        jobs.extend(day_jobs(entry, flags.examples, flags.parts.part, &params)?);
+    }
+    if let Some(user) = &flags.user {
+        jobs.retain(|job| job.source.user() == *user);
+    }
+
+    let threads = flags.jobs.unwrap_or_else(runner::default_threads);
+    let (mut results, timing) = runner::run_all(jobs, threads, flags.limit.timeout);
+    for result in &mut results {
+        if let Some(output) = result.source.output() {
+            check(result, &output, flags.record)?;
+        }
+    }
+    report::print(flags.format.unwrap_or(Format::Table), &results);
+    let cpu = match timing.summed_cpu {
+        Some(cpu) => format! {", {cpu:.2?} summed CPU time"},
+        None => String::new(),
+    };
+    eprintln! {
+        "{} input(s) on {threads} thread(s): {:.2?} wall{cpu}",
+        results.len(),
+        timing.wall
+    };
+    let running = runner::runaways();
+    if running > 0 {
+        eprintln! {"{running} parse(s) or part(s) that timed out are still running in the background"};
+    }
+    Ok(results.iter().map(DayResult::failures).sum())
+}
+
+/// Prints the answer to each part, reporting errors per part so one failing
+/// part doesn't hide the other's answer.
+fn run_one(day: usize, source: Source, flags: &RunArgs) -> anyhow::Result<usize> {
+    let entry = registry::find(day)?;
+    let output = source.output();
+    let job = runner::Job {
+        day,
+        part: flags.parts.part,
+        params: source.params(&flags.params.overrides())?,
+        source,
+        create: entry.try_create,
+    };
+    let mut result = runner::run(job, flags.limit.timeout);
+    if let Some(output) = output {
+        check(&mut result, &output, flags.record)?;
+    }
+    if let Some(format) = flags.format {
+        let errors = result.parts.iter().filter(|p| p.answer.is_err()).count();
+        let failures = if result.parse.is_err() {
+            1
+        } else {
+            result.failures() + errors
+        };
+        report::print(format, &[result]);
+        return Ok(failures);
+    }
+
+    let mut failures = result.failures();
+    result.parse?;
+    for part in &result.parts {
+        match &part.answer {
+            Ok(answer) => println! {"{answer}"},
+            Err(e) => {
+                eprintln! {"part {}: {e:#}", part.part};
+                failures += 1;
+            }
+        }
+        if let Check::Fail { expected } = &part.check {
+            eprintln! {"part {}: expected {expected}", part.part};
+        }
+    }
+    Ok(failures)
+}
+
+fn run_bench(args: BenchArgs) -> anyhow::Result<()> {
+    let entries = match args.day {
+        Some(day) => vec![registry::find(day)?],
+        None => registry::puzzles().collect(),
+    };
+    let options = bench::Options {
+        runs: args.runs,
+        budget: args.budget,
+    };
+
+    let mut results = Vec::new();
+    for entry in entries {
+        let path = runner::puzzle_dir(entry.day).join(format! {"{}.input", args.user});
+        let lines = match runner::read_lines(&path) {
+            Ok(lines) => lines,
+            Err(e) => {
+                eprintln! {"skipping day {:02}: {e:#}", entry.day};
+                continue;
+            }
+        };
+        let params = Source::File(path).params(&args.params.overrides())?;
+        results.push(bench::bench(
+            entry.day,
+            args.parts.part,
+            &lines,
+            entry.try_create,
+            &params,
+            &options,
+        ));
+    }
+    bench::print_table(&results);
+    Ok(())
+}
+
+fn fetch(args: FetchArgs) -> anyhow::Result<()> {
+    let paths = args
+        .day
+        .iter()
+        .map(|&day| {
+            (
+                day,
+                runner::puzzle_dir(day).join(format! {"{}.input", args.user}),
+            )
+        })
+        .collect::<Vec<_>>();
+    // Don't insist on a cookie when everything is already cached.
+    if paths.iter().all(|(_, path)| aoc::is_cached(path)) {
+        for (_, path) in paths {
+            println! {"{} already exists", path.display()};
+        }
+        return Ok(());
+    }
+
+    let mut site = args.site.connect(args.interval)?;
+    for (day, path) in paths {
+        match aoc::fetch(&mut site, day, &path)? {
+            aoc::Fetched::Cached => println! {"{} already exists", path.display()},
+            aoc::Fetched::Downloaded => println! {"saved {}", path.display()},
+        }
+    }
+    Ok(())
+}
+
+fn submit(args: SubmitArgs) -> anyhow::Result<()> {
+    let input = runner::puzzle_dir(args.day).join(format! {"{}.input", args.user});
+    let answer = match args.answer {
+        Some(answer) => answer,
+        None => {
+            let entry = registry::find(args.day)?;
+            let part = if args.part == 1 { Part::One } else { Part::Two };
+            let source = Source::File(input.clone());
+            let job = runner::Job {
+                day: args.day,
+                part,
+                params: source.params(&Params::default())?,
+                source,
+                create: entry.try_create,
+            };
+            let result = runner::run(job, None);
+            result.parse?;
+            let answer = result
+                .parts
+                .into_iter()
+                .next()
+                .expect("one part was run")
+                .answer?;
+            println! {"part {}: {answer}", args.part};
+            answer
+        }
+    };
+
+    let mut site = args.site.connect(Duration::ZERO)?;
+    let output = input.with_extension("output");
+    match aoc::submit(&mut site, args.day, args.part, &answer, &output)? {
+        aoc::Outcome::Judged(Verdict::Right) => println! {"that's the right answer"},
+        aoc::Outcome::Judged(verdict) => {
+            anyhow::bail! {"{answer} is not the right answer ({})", verdict.as_str()}
+        }
+        aoc::Outcome::Known(Verdict::Right) => println! {"{answer} is already known to be right"},
+        aoc::Outcome::Known(verdict) => anyhow::bail! {
+            "{answer} was already rejected ({}); not submitting it again",
+            verdict.as_str()
+        },
+        aoc::Outcome::Disproved(reason) => anyhow::bail! {"{reason}; not submitting it"},
+        aoc::Outcome::TooSoon(left) => {
+            anyhow::bail! {"answered too recently; try again in {left}"}
+        }
+        aoc::Outcome::WrongLevel => anyhow::bail! {
+            "part {} can't be answered: it is locked or was already solved",
+            args.part
+        },
+    }
+    Ok(())
+}
+
+fn watch(args: WatchArgs) -> anyhow::Result<()> {
+    let entry = registry::find(args.day)?;
+    let params = args.params.overrides();
+    watch::watch(&runner::puzzle_dir(args.day), args.interval, || {
+        let mut jobs = day_jobs(entry, true, args.parts.part, &params)?;
+        jobs.extend(day_jobs(entry, false, args.parts.part, &params)?);
+        let (mut results, _) = runner::run_all(jobs, runner::default_threads(), args.limit.timeout);
+        for result in &mut results {
+            if let Some(output) = result.source.output() {
+                check(result, &output, false)?;
+            }
+        }
+        Ok(results)
+    })
 }
 
-impl read::HasFile for Args {
-    fn file(&self) -> String {
-        self.input.clone()
-    }
-}
-
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let (input, flags) = read::input_with_type::<Args>(None);
-    let solver: Box<dyn Solver> = match flags.day {
-        // BEGIN_SOLVER_LIST
-        0 => day00::Day00::try_create(input),
-        1 => day01::Day01::try_create(input),
-        2 => day02::Day02::try_create(input),
-        3 => day03::Day03::try_create(input),
-        4 => day04::Day04::try_create(input),
-        5 => day05::Day05::try_create(input),
-        6 => day06::Day06::try_create(input),
-        7 => day07::Day07::try_create(input),
-        8 => day08::Day08::try_create(input),
-        9 => day09::Day09::try_create(input),
-        // END_SOLVER_LIST
-        _ => panic! {"Failed to find solver"},
-    }?;
-    println! {"{}", solver.part_one()?};
-    println! {"{}", solver.part_two()?};
+fn repl(args: ReplArgs) -> anyhow::Result<()> {
+    let entry = registry::find(args.day)?;
+    repl::repl(
+        entry,
+        Source::File(repl::resolve(args.day, &args.input)),
+        args.params.overrides(),
+        args.limit.timeout,
+        std::io::stdin().lock(),
+        &mut std::io::stdout(),
+    )
+}
+
+fn run(flags: RunArgs) -> anyhow::Result<()> {
+    if flags.list {
+        list();
+        return Ok(());
+    }
+
+    let failures = if flags.all || flags.examples {
+        run_all(&flags)?
+    } else {
+        let source = match (&flags.input, &flags.text) {
+            (Some(input), _) => Source::from_arg(input),
+            (None, Some(text)) => Source::Text(text.clone()),
+            (None, None) => unreachable! {"clap requires --input or --text without --all"},
+        };
+        let Some(day) = flags.day else {
+            unreachable! {"clap requires --day without --all or --list"}
+        };
+        run_one(day, source, &flags)?
+    };
+
+    if failures > 0 {
+        anyhow::bail! {"{failures} part(s) failed or did not match the expected output"};
+    }
     Ok(())
 }
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    match args.command {
+        Some(Command::Bench(bench)) => run_bench(bench),
+        Some(Command::Fetch(fetch_args)) => fetch(fetch_args),
+        Some(Command::Submit(submit_args)) => submit(submit_args),
+        Some(Command::Watch(watch_args)) => watch(watch_args),
+        Some(Command::Repl(repl_args)) => repl(repl_args),
+        Some(Command::NewDay(new_day)) => {
+            scaffold::new_day(Path::new("."), new_day.day, &new_day.title)?;
+            println! {"created src/day{:02}.rs; add inputs to puzzles/day{0:02}/", new_day.day};
+            Ok(())
+        }
+        None => run(args.run),
+    }
+}
@@ -0,0 +1,93 @@
+/// Runs `f`, returning the most heap it had allocated at any one time on this
+/// thread, over what was already allocated when it started. Without the
+/// `memory` feature nothing is counted and the peak is `None`.
+#[cfg(feature = "memory")]
+pub fn peak<T>(f: impl FnOnce() -> T) -> (T, Option<usize>) {
+    let start = counting::CURRENT.get();
+    counting::PEAK.set(start);
+    let result = f();
+    let peak = counting::PEAK.get() - start;
+    (result, Some(peak.max(0) as usize))
+}
+
+#[cfg(not(feature = "memory"))]
+pub fn peak<T>(f: impl FnOnce() -> T) -> (T, Option<usize>) {
+    (f(), None)
+}
+
+/// Counts allocations per thread, so that work running on other threads at
+/// the same time doesn't count towards a measurement.
+#[cfg(feature = "memory")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    thread_local! {
+        // Signed, as memory can be freed on a different thread to the one
+        // that allocated it.
+        pub static CURRENT: Cell<isize> = const { Cell::new(0) };
+        pub static PEAK: Cell<isize> = const { Cell::new(0) };
+    }
+
+    fn record(delta: isize) {
+        let now = CURRENT.get() + delta;
+        CURRENT.set(now);
+        if now > PEAK.get() {
+            PEAK.set(now);
+        }
+    }
+
+    struct Counting;
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc(layout) };
+            if !ptr.is_null() {
+                record(layout.size() as isize);
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc_zeroed(layout) };
+            if !ptr.is_null() {
+                record(layout.size() as isize);
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) };
+            record(-(layout.size() as isize));
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new = unsafe { System.realloc(ptr, layout, new_size) };
+            if !new.is_null() {
+                record(new_size as isize - layout.size() as isize);
+            }
+            new
+        }
+    }
+}
+
+#[cfg(all(test, feature = "memory"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_measures_the_peak() {
+        let (len, peak) = peak(|| {
+            let big = vec![0_u8; 1 << 20];
+            drop(big);
+            let small = vec![0_u8; 1 << 10];
+            small.len()
+        });
+        assert_eq! {len, 1 << 10};
+        let peak = peak.unwrap();
+        assert! {(1 << 20..1 << 21).contains(&peak), "{peak}"};
+    }
+}
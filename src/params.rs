@@ -0,0 +1,160 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use anyhow::Context;
+use itertools::Itertools;
+
+/// Named settings passed to `try_create` for things the puzzle text states
+/// differently for the example and the real input, like how many steps to
+/// take. They come from `--param name=value`, a `<input>.params` file of
+/// `name=value` lines next to an input, and the test tables.
+#[derive(Debug, Default)]
+pub struct Params {
+    values: BTreeMap<String, String>,
+    /// Every name `get_or` was asked for, so that `check_used` can catch
+    /// misspelt parameters.
+    asked: Mutex<BTreeSet<String>>,
+}
+
+impl Clone for Params {
+    fn clone(&self) -> Self {
+        Params::from_iter(self.values.clone())
+    }
+}
+
+impl PartialEq for Params {
+    fn eq(&self, other: &Params) -> bool {
+        self.values == other.values
+    }
+}
+
+impl Eq for Params {}
+
+impl Params {
+    /// A missing file just means the input needs no parameters.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Params::default());
+        }
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format! {"could not read {}", path.display()})?;
+        contents
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(parse_pair)
+            .collect::<anyhow::Result<Params>>()
+            .with_context(|| format! {"could not parse {}", path.display()})
+    }
+
+    /// Adds `other` on top of `self`, its values winning.
+    pub fn merge(mut self, other: &Params) -> Self {
+        self.values.extend(other.values.clone());
+        self
+    }
+
+    /// The parameter's value, or `default` if it wasn't given.
+    pub fn get_or<T>(&self, name: &str, default: T) -> anyhow::Result<T>
+    where
+        T: FromStr,
+        T::Err: std::error::Error + Send + Sync + 'static,
+    {
+        self.asked
+            .lock()
+            .expect("no lock holder panics")
+            .insert(name.to_string());
+        match self.values.get(name) {
+            Some(value) => value
+                .parse()
+                .with_context(|| format! {"parameter {name} has a bad value {value:?}"}),
+            None => Ok(default),
+        }
+    }
+
+    /// Fails if a parameter was given that `get_or` was never asked for, as
+    /// the solver would otherwise quietly carry on without it.
+    pub fn check_used(&self) -> anyhow::Result<()> {
+        let asked = self.asked.lock().expect("no lock holder panics");
+        let unknown = self
+            .values
+            .keys()
+            .filter(|name| !asked.contains(*name))
+            .collect_vec();
+        if unknown.is_empty() {
+            return Ok(());
+        }
+        let expected = if asked.is_empty() {
+            "this day takes none".to_string()
+        } else {
+            format! {"expected {}", asked.iter().join(", ")}
+        };
+        anyhow::bail! {"unknown parameter(s) {}: {expected}", unknown.iter().join(", ")}
+    }
+}
+
+impl FromIterator<(String, String)> for Params {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        Params {
+            values: iter.into_iter().collect(),
+            asked: Mutex::default(),
+        }
+    }
+}
+
+/// Parses one `name=value`, as given to `--param`.
+pub fn parse_pair(pair: &str) -> anyhow::Result<(String, String)> {
+    let Some((name, value)) = pair.split_once('=') else {
+        anyhow::bail! {"expected name=value, got {pair:?}"};
+    };
+    let (name, value) = (name.trim(), value.trim());
+    if name.is_empty() {
+        anyhow::bail! {"parameter in {pair:?} has no name"};
+    }
+    Ok((name.to_string(), value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_typed_values() -> anyhow::Result<()> {
+        let params = ["limit=10", " start = 50 "]
+            .into_iter()
+            .map(parse_pair)
+            .collect::<anyhow::Result<Params>>()?;
+        assert_eq! {params.get_or("limit", 1000_usize)?, 10};
+        assert_eq! {params.get_or("start", 0_u16)?, 50};
+        assert_eq! {params.get_or("missing", 7_u8)?, 7};
+        assert_eq! {params.get_or("start", 0_u8)?, 50};
+        assert! {params.get_or("limit", true).is_err()};
+
+        assert! {parse_pair("limit").is_err()};
+        assert! {parse_pair("=10").is_err()};
+
+        let cli = Params::from_iter([("limit".to_string(), "3".to_string())]);
+        assert_eq! {params.merge(&cli).get_or("limit", 0_usize)?, 3};
+        Ok(())
+    }
+
+    #[test]
+    fn it_rejects_parameters_nobody_asked_for() -> anyhow::Result<()> {
+        let params = ["limit=10", "limt=5"]
+            .into_iter()
+            .map(parse_pair)
+            .collect::<anyhow::Result<Params>>()?;
+        assert_eq! {
+            params.check_used().unwrap_err().to_string(),
+            "unknown parameter(s) limit, limt: this day takes none"
+        };
+        params.get_or("limit", 1000_usize)?;
+        params.get_or("start", 50_i32)?;
+        assert_eq! {
+            params.check_used().unwrap_err().to_string(),
+            "unknown parameter(s) limt: expected limit, start"
+        };
+        assert! {Params::default().check_used().is_ok()};
+        Ok(())
+    }
+}
@@ -0,0 +1,299 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use anyhow::Context;
+
+/// Reads one line of input from left to right, keeping track of where it is
+/// so that errors can say which line and column went wrong and what was
+/// found there. The runner adds which input file it was.
+#[derive(Debug, Clone)]
+pub struct Cursor<'a> {
+    line: usize,
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    /// `line` counts from 1, as editors do.
+    pub fn new(line: usize, text: &'a str) -> Self {
+        Cursor { line, text, pos: 0 }
+    }
+
+    /// The column of the next character, counting from 1.
+    pub fn column(&self) -> usize {
+        self.text[..self.pos].chars().count() + 1
+    }
+
+    /// Everything not read yet.
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    /// An error at the current position.
+    pub fn error(&self, message: impl Display) -> anyhow::Error {
+        anyhow::anyhow! {"line {}, column {}: {message}", self.line, self.column()}
+    }
+
+    /// An error saying what should have come next, and what came instead.
+    pub fn expected(&self, what: impl Display) -> anyhow::Error {
+        self.error(format! {"expected {what}, found {}", self.found()})
+    }
+
+    /// The text at the cursor, up to the next space, for error messages.
+    fn found(&self) -> String {
+        let word = self
+            .rest()
+            .split(char::is_whitespace)
+            .next()
+            .unwrap_or_default();
+        match self.peek() {
+            None => "the end of the line".to_string(),
+            Some(' ') => "a space".to_string(),
+            Some(c) if word.is_empty() => format! {"{c:?}"},
+            Some(_) if word.chars().count() > 20 => {
+                format! {"{:?}...", word.chars().take(20).collect::<String>()}
+            }
+            Some(_) => format! {"{word:?}"},
+        }
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    pub fn char(&mut self) -> anyhow::Result<char> {
+        let c = self.peek().ok_or_else(|| self.expected("a character"))?;
+        self.pos += c.len_utf8();
+        Ok(c)
+    }
+
+    /// Skips `prefix` if it comes next, saying whether it did.
+    pub fn eat(&mut self, prefix: &str) -> bool {
+        let found = self.rest().starts_with(prefix);
+        if found {
+            self.pos += prefix.len();
+        }
+        found
+    }
+
+    pub fn expect(&mut self, prefix: &str) -> anyhow::Result<()> {
+        if self.eat(prefix) {
+            Ok(())
+        } else {
+            Err(self.expected(format! {"{prefix:?}"}))
+        }
+    }
+
+    /// Skips spaces, but not tabs or other whitespace, which puzzles that
+    /// line things up in columns can't make sense of.
+    pub fn skip_spaces(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start_matches(' ').len();
+    }
+
+    pub fn digit(&mut self) -> anyhow::Result<u8> {
+        match self.peek().and_then(|c| c.to_digit(10)) {
+            Some(digit) => {
+                self.pos += 1;
+                Ok(digit as u8)
+            }
+            None => Err(self.expected("a digit")),
+        }
+    }
+
+    /// A number in decimal, with a `-` in front if it is negative.
+    pub fn number<T>(&mut self) -> anyhow::Result<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let rest = self.rest();
+        let sign = usize::from(rest.starts_with('-'));
+        let len = sign
+            + rest[sign..]
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len() - sign);
+        if len == sign {
+            return Err(self.expected("a number"));
+        }
+        let number = rest[..len]
+            .parse()
+            .map_err(|e| self.error(format! {"could not read {:?}: {e}", &rest[..len]}))?;
+        self.pos += len;
+        Ok(number)
+    }
+
+    /// Fails if anything is left on the line.
+    pub fn end(&self) -> anyhow::Result<()> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.expected("the end of the line"))
+        }
+    }
+}
+
+/// Reads every line that isn't blank with `f`, which has to read all of it.
+pub fn lines<T>(
+    input: impl IntoIterator<Item = String>,
+    f: impl FnMut(&mut Cursor) -> anyhow::Result<T>,
+) -> anyhow::Result<Vec<T>> {
+    numbered(
+        input
+            .into_iter()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| (i + 1, line)),
+        f,
+    )
+}
+
+fn numbered<T>(
+    lines: impl Iterator<Item = (usize, impl AsRef<str>)>,
+    mut f: impl FnMut(&mut Cursor) -> anyhow::Result<T>,
+) -> anyhow::Result<Vec<T>> {
+    lines
+        .map(|(number, line)| {
+            let mut cursor = Cursor::new(number, line.as_ref());
+            let value = f(&mut cursor)?;
+            cursor.end()?;
+            Ok(value)
+        })
+        .collect()
+}
+
+/// A run of lines between blank lines, from `sections`.
+#[derive(Debug)]
+pub struct Section {
+    name: &'static str,
+    /// The line number of the first line.
+    first: usize,
+    lines: Vec<String>,
+}
+
+impl Section {
+    /// Each line with its line number in the whole input.
+    pub fn lines(&self) -> impl Iterator<Item = (usize, &str)> {
+        (self.first..).zip(self.lines.iter().map(String::as_str))
+    }
+
+    /// Reads every line with `f`, which has to read all of it.
+    pub fn parse<T>(
+        &self,
+        f: impl FnMut(&mut Cursor) -> anyhow::Result<T>,
+    ) -> anyhow::Result<Vec<T>> {
+        numbered(self.lines(), f).with_context(|| format! {"could not read the {}", self.name})
+    }
+}
+
+/// Splits the input at blank lines into one section for each of `names`,
+/// which describe what each section holds for error messages. Blank lines
+/// before, after and between sections are skipped.
+pub fn sections<const N: usize>(
+    input: impl IntoIterator<Item = String>,
+    names: [&'static str; N],
+) -> anyhow::Result<[Section; N]> {
+    let mut sections = Vec::<Section>::new();
+    let mut blank = true;
+    for (i, line) in input.into_iter().enumerate() {
+        if line.trim().is_empty() {
+            blank = true;
+            continue;
+        }
+        if !blank && let Some(section) = sections.last_mut() {
+            section.lines.push(line);
+        } else {
+            sections.push(Section {
+                name: "",
+                first: i + 1,
+                lines: vec![line],
+            });
+        }
+        blank = false;
+    }
+    if sections.len() != N {
+        anyhow::bail! {
+            "expected {N} section(s) separated by blank lines ({}), found {}",
+            names.join(", "),
+            sections.len()
+        };
+    }
+    for (section, name) in sections.iter_mut().zip(names) {
+        section.name = name;
+    }
+    Ok(sections.try_into().expect("there are N sections"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(line: &mut Cursor) -> anyhow::Result<(i64, u8)> {
+        let x = line.number()?;
+        line.expect(",")?;
+        Ok((x, line.number()?))
+    }
+
+    fn error(input: &[&str]) -> String {
+        let input = input.iter().map(|l| l.to_string());
+        parse_error(lines(input, pair))
+    }
+
+    fn parse_error<T: std::fmt::Debug>(result: anyhow::Result<T>) -> String {
+        result.unwrap_err().to_string()
+    }
+
+    #[test]
+    fn it_reads_lines() -> anyhow::Result<()> {
+        let input = ["1,2", "", "-30,4"].map(String::from);
+        assert_eq! {lines(input, pair)?, [(1, 2), (-30, 4)]};
+
+        let mut line = Cursor::new(1, "  7x");
+        line.skip_spaces();
+        assert_eq! {line.column(), 3};
+        assert_eq! {line.digit()?, 7};
+        assert! {!line.eat("y") && line.eat("x")};
+        assert! {line.end().is_ok()};
+
+        let mut line = Cursor::new(1, " \t1");
+        line.skip_spaces();
+        assert_eq! {line.column(), 2};
+        Ok(())
+    }
+
+    #[test]
+    fn it_splits_sections() -> anyhow::Result<()> {
+        let input = ["", "1,2", "3,4", "", "", "5", "", ""].map(String::from);
+        let [pairs, numbers] = sections(input.clone(), ["pairs", "numbers"])?;
+        assert_eq! {pairs.parse(pair)?, [(1, 2), (3, 4)]};
+        assert_eq! {numbers.lines().collect::<Vec<_>>(), [(6, "5")]};
+        assert_eq! {
+            format! {"{:#}", numbers.parse(pair).unwrap_err()},
+            "could not read the numbers: line 6, column 2: expected \",\", found the end of the line"
+        };
+
+        assert_eq! {
+            parse_error(sections(input, ["pairs"])),
+            "expected 1 section(s) separated by blank lines (pairs), found 2"
+        };
+        assert! {sections([] as [String; 0], ["pairs"]).is_err()};
+        Ok(())
+    }
+
+    #[test]
+    fn it_says_where_lines_went_wrong() {
+        assert_eq! {error(&["1,2", "3;4"]), "line 2, column 2: expected \",\", found \";4\""};
+        assert_eq! {error(&["1,2", "", "x,4"]), "line 3, column 1: expected a number, found \"x,4\""};
+        assert_eq! {error(&["1,300"]), "line 1, column 3: could not read \"300\": number too large to fit in target type"};
+        assert_eq! {error(&["1,2 3"]), "line 1, column 4: expected the end of the line, found a space"};
+        assert_eq! {error(&["1,"]), "line 1, column 3: expected a number, found the end of the line"};
+        assert_eq! {
+            parse_error(Cursor::new(1, "").digit()),
+            "line 1, column 1: expected a digit, found the end of the line"
+        };
+    }
+}
@@ -0,0 +1,53 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Applies `f` to every item on up to `threads` scoped threads, each worker
+/// taking the next unclaimed item, and returns the results in item order.
+pub fn map<T: Sync, R: Send>(items: &[T], threads: usize, f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let next = AtomicUsize::new(0);
+    let mut results = std::thread::scope(|scope| {
+        let workers = (0..threads.clamp(1, items.len().max(1)))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(i) else {
+                            break;
+                        };
+                        done.push((i, f(item)));
+                    }
+                    done
+                })
+            })
+            .collect::<Vec<_>>();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("pool worker panicked"))
+            .collect::<Vec<_>>()
+    });
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, r)| r).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[test]
+    fn it_keeps_results_in_order() {
+        let items = (0..50).collect::<Vec<u64>>();
+        let threads = Mutex::new(HashSet::new());
+        let squares = map(&items, 4, |&i| {
+            threads.lock().unwrap().insert(std::thread::current().id());
+            // Finish out of order.
+            std::thread::sleep(Duration::from_micros(50 - i));
+            i * i
+        });
+        assert_eq! {squares, items.iter().map(|i| i * i).collect::<Vec<_>>()};
+        assert! {threads.lock().unwrap().len() > 1};
+        assert! {map(&[] as &[u8], 4, |&b| b).is_empty()};
+    }
+}
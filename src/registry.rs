@@ -0,0 +1,95 @@
+use std::fmt::Display;
+
+use itertools::Itertools;
+use solver::Solver;
+
+use crate::params::Params;
+use crate::runner::Input;
+
+pub type Constructor = fn(Input, &Params) -> anyhow::Result<Box<dyn Solver + Send + Sync>>;
+
+/// Prints a solver's parsed input, or gives `None` if it isn't this day's.
+pub type Show = fn(&dyn Solver) -> Option<String>;
+
+pub struct Entry {
+    pub day: usize,
+    pub title: &'static str,
+    pub try_create: Constructor,
+    /// Set for days whose solver implements `Display`, for the repl's `show`.
+    pub show: Option<Show>,
+}
+
+impl Entry {
+    const fn new(day: usize, title: &'static str, try_create: Constructor) -> Self {
+        Entry {
+            day,
+            title,
+            try_create,
+            show: None,
+        }
+    }
+
+    /// Lets the repl print the parsed input of `T`, the day's solver.
+    const fn shows<T: Display + 'static>(self) -> Self {
+        Entry {
+            show: Some(shown::<T>),
+            ..self
+        }
+    }
+
+    pub fn name(&self) -> String {
+        format! {"Day{:02}", self.day}
+    }
+}
+
+// The `new-day` command splices new days in between the markers, keeping day order.
+// It works line by line, so each day's entry has to stay on one line.
+#[rustfmt::skip]
+pub static SOLVERS: &[Entry] = &[
+    // BEGIN_SOLVER_LIST
+    Entry::new(0, "Template", crate::day00::Day00::try_create),
+    Entry::new(1, "Secret Entrance", crate::day01::Day01::try_create),
+    Entry::new(2, "Gift Shop", crate::day02::Day02::try_create),
+    Entry::new(3, "Lobby", crate::day03::Day03::try_create),
+    Entry::new(4, "Printing Department", crate::day04::Day04::try_create).shows::<crate::day04::Day04>(),
+    Entry::new(5, "Cafeteria", crate::day05::Day05::try_create),
+    Entry::new(6, "Trash Compactor", crate::day06::Day06::try_create).shows::<crate::day06::Day06>(),
+    Entry::new(7, "Laboratories", crate::day07::Day07::try_create).shows::<crate::day07::Day07>(),
+    Entry::new(8, "Playground", crate::day08::Day08::try_create),
+    Entry::new(9, "Movie Theater", crate::day09::Day09::try_create),
+    // END_SOLVER_LIST
+];
+
+pub fn find(day: usize) -> anyhow::Result<&'static Entry> {
+    SOLVERS.iter().find(|e| e.day == day).ok_or_else(|| {
+        anyhow::anyhow! {
+            "no solver registered for day {day} (registered: {})",
+            puzzles().map(|e| e.day).join(", ")
+        }
+    })
+}
+
+fn shown<T: Display + 'static>(solver: &dyn Solver) -> Option<String> {
+    solver.as_any().downcast_ref::<T>().map(T::to_string)
+}
+
+/// Every registered day except the day 0 template.
+pub fn puzzles() -> impl Iterator<Item = &'static Entry> {
+    SOLVERS.iter().filter(|e| e.day > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_are_unique_and_ordered() {
+        assert! {SOLVERS.iter().map(|e| e.day).tuple_windows().all(|(l, r)| l < r)};
+    }
+
+    #[test]
+    fn unknown_days_are_an_error() {
+        assert_eq! {find(3).unwrap().name(), "Day03"};
+        assert! {find(26).is_err()};
+    }
+}
@@ -0,0 +1,246 @@
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use clap::ValueEnum;
+use solver::Solver;
+
+use crate::answers::{Check, Expected};
+use crate::params::Params;
+use crate::registry::Entry;
+use crate::runner::{self, Part, Source};
+
+const HELP: &str = "\
+1, 2, both        run part one, part two or both again
+run [1|2|both]    the same, running both parts by default
+input NAME|PATH   parse puzzles/dayNN/NAME.input, or the file at PATH, instead
+reload            parse the current input again, picking up edits to it
+show              print the parsed input, for days that implement Display
+help              print this
+quit              leave; so does end of input";
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Run(Part),
+    Input(String),
+    Reload,
+    Show,
+    Help,
+    Quit,
+}
+
+/// Reads one line typed at the prompt, or `None` for a blank line.
+fn parse(line: &str) -> anyhow::Result<Option<Command>> {
+    let line = line.trim();
+    let (word, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let rest = rest.trim();
+    let part = |s: &str| {
+        Part::from_str(s, true).map_err(|_| anyhow::anyhow! {"expected 1, 2 or both, got {s:?}"})
+    };
+    let command = match (word, rest) {
+        ("", _) => return Ok(None),
+        ("1" | "2" | "both", "") | ("run", _) => Command::Run(match (word, rest) {
+            ("run", "") => Part::Both,
+            ("run", rest) => part(rest)?,
+            (word, _) => part(word)?,
+        }),
+        ("input", "") => anyhow::bail! {"input needs a name or a path"},
+        ("input", rest) => Command::Input(rest.to_string()),
+        ("reload", "") => Command::Reload,
+        ("show", "") => Command::Show,
+        ("help" | "?", "") => Command::Help,
+        ("quit" | "exit" | "q", "") => Command::Quit,
+        (word, "") => anyhow::bail! {"unknown command {word:?}; try help"},
+        (word, _) => anyhow::bail! {"{word} takes no arguments"},
+    };
+    Ok(Some(command))
+}
+
+/// A bare name like `example` means `puzzles/dayNN/example.input`; anything
+/// else is taken as a path.
+pub fn resolve(day: usize, input: &str) -> PathBuf {
+    if input.contains(['/', '.']) {
+        PathBuf::from(input)
+    } else {
+        runner::puzzle_dir(day).join(format! {"{input}.input"})
+    }
+}
+
+/// One day's solver, kept parsed between commands.
+struct Session {
+    entry: &'static Entry,
+    overrides: Params,
+    timeout: Option<Duration>,
+    source: Source,
+    solver: anyhow::Result<Arc<dyn Solver + Send + Sync>>,
+}
+
+impl Session {
+    fn load(&mut self, source: Source, out: &mut impl Write) -> anyhow::Result<()> {
+        self.source = source;
+        let create = self.entry.try_create;
+        let parsed = self.source.lines().and_then(|lines| {
+            let params = self.source.params(&self.overrides)?;
+            let measured = runner::limited(self.timeout, move || {
+                runner::construct(create, Box::new(lines.into_iter()), &params).map(Arc::from)
+            });
+            Ok((measured.result, measured.elapsed))
+        });
+        self.solver = match parsed {
+            Ok((Ok(solver), elapsed)) => {
+                writeln! {out, "parsed {} in {elapsed:.2?}", self.source}?;
+                Ok(solver)
+            }
+            Ok((Err(e), _)) | Err(e) => {
+                writeln! {out, "could not parse {}: {e:#}", self.source}?;
+                Err(e)
+            }
+        };
+        Ok(())
+    }
+
+    fn run(&self, part: Part, out: &mut impl Write) -> anyhow::Result<()> {
+        let Ok(solver) = &self.solver else {
+            writeln! {out, "{} did not parse; fix it and reload, or pick another input", self.source}?;
+            return Ok(());
+        };
+        // Loaded afresh each time, in case an answer was recorded meanwhile.
+        let expected = match self.source.output() {
+            Some(output) => Expected::load(&output)?,
+            None => Expected::default(),
+        };
+        for &number in part.numbers() {
+            let solver = Arc::clone(solver);
+            let measured =
+                runner::limited(self.timeout, move || runner::solve(solver.as_ref(), number));
+            let check = expected.check(number, &measured.result);
+            match &measured.result {
+                Ok(answer) => write! {out, "part {number}: {answer}"}?,
+                Err(e) => write! {out, "part {number} failed: {e:#}"}?,
+            }
+            write! {out, " in {:.2?}", measured.elapsed}?;
+            match check {
+                Check::Pass => writeln! {out, ", as expected"}?,
+                Check::Fail { expected } => writeln! {out, ", expected {expected}"}?,
+                Check::Unknown => writeln! {out}?,
+            }
+        }
+        Ok(())
+    }
+
+    fn show(&self, out: &mut impl Write) -> anyhow::Result<()> {
+        match &self.solver {
+            Ok(solver) => match self.entry.show.and_then(|show| show(solver.as_ref())) {
+                Some(shown) => write! {out, "{}", shown.trim_end()}?,
+                None => write! {out, "{} can't print its parsed input", self.entry.name()}?,
+            },
+            Err(_) => write! {out, "{} did not parse", self.source}?,
+        }
+        writeln! {out}?;
+        Ok(())
+    }
+}
+
+/// Parses `source` once and then runs commands read from `input` against it
+/// until `quit` or the end of `input`. A command that fails is reported and
+/// the session carries on.
+pub fn repl(
+    entry: &'static Entry,
+    source: Source,
+    overrides: Params,
+    timeout: Option<Duration>,
+    input: impl BufRead,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    let mut session = Session {
+        entry,
+        overrides,
+        timeout,
+        source: source.clone(),
+        solver: Err(anyhow::anyhow! {"not parsed yet"}),
+    };
+    session.load(source, out)?;
+
+    let prompt = format! {"{}> ", entry.name().to_lowercase()};
+    let mut lines = input.lines();
+    loop {
+        write! {out, "{prompt}"}?;
+        out.flush()?;
+        let Some(line) = lines.next() else {
+            writeln! {out}?;
+            return Ok(());
+        };
+        let command = match parse(&line?) {
+            Ok(Some(command)) => command,
+            Ok(None) => continue,
+            Err(e) => {
+                writeln! {out, "{e:#}"}?;
+                continue;
+            }
+        };
+        match command {
+            Command::Run(part) => session.run(part, out)?,
+            Command::Input(input) => {
+                let path = resolve(entry.day, &input);
+                session.load(Source::File(path), out)?;
+            }
+            Command::Reload => session.load(session.source.clone(), out)?,
+            Command::Show => session.show(out)?,
+            Command::Help => writeln! {out, "{HELP}"}?,
+            Command::Quit => return Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn it_parses_commands() -> anyhow::Result<()> {
+        assert_eq! {parse("  ")?, None};
+        assert_eq! {parse("1")?, Some(Command::Run(Part::One))};
+        assert_eq! {parse("run")?, Some(Command::Run(Part::Both))};
+        assert_eq! {parse("run 2")?, Some(Command::Run(Part::Two))};
+        assert_eq! {parse("input  my input.txt ")?, Some(Command::Input("my input.txt".into()))};
+        assert_eq! {parse("show")?, Some(Command::Show)};
+        assert! {parse("run 3").is_err()};
+        assert! {parse("input").is_err()};
+        assert! {parse("show all").is_err()};
+        assert! {parse("solve").is_err()};
+        Ok(())
+    }
+
+    #[test]
+    fn it_resolves_input_names() {
+        assert_eq! {resolve(7, "example"), PathBuf::from("puzzles/day07/example.input")};
+        assert_eq! {resolve(7, "other.input"), PathBuf::from("other.input")};
+        assert_eq! {resolve(7, "/tmp/day7"), PathBuf::from("/tmp/day7")};
+    }
+
+    #[test]
+    fn it_keeps_the_parse_between_commands() -> anyhow::Result<()> {
+        let entry = registry::find(7)?;
+        let commands = "show\n1\nbogus\ninput missing\n2\ninput example\nrun 2\n";
+        let mut out = Vec::new();
+        repl(
+            entry,
+            Source::Text("..S..\n.....\n..^..".into()),
+            Params::default(),
+            None,
+            commands.as_bytes(),
+            &mut out,
+        )?;
+        let out = String::from_utf8(out)?;
+        assert! {out.contains("..S..\n.....\n..^..\n"), "{out}"};
+        assert! {out.contains("part 1: 1 in "), "{out}"};
+        assert! {out.contains("unknown command \"bogus\""), "{out}"};
+        assert! {out.contains("could not parse puzzles/day07/missing.input"), "{out}"};
+        assert! {out.contains("missing.input did not parse"), "{out}"};
+        assert! {out.contains("part 2: 40 in "), "{out}"};
+        assert! {out.contains(", as expected"), "{out}"};
+        Ok(())
+    }
+}
@@ -0,0 +1,321 @@
+use std::time::Duration;
+
+use crate::answers::Check;
+use crate::runner::DayResult;
+use crate::table;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+pub fn print(format: Format, results: &[DayResult]) {
+    match format {
+        Format::Table => print_table(results),
+        Format::Json => print! {"{}", json(&records(results))},
+        Format::Csv => print! {"{}", csv(&records(results))},
+    }
+}
+
+/// A byte count in the largest binary unit that keeps it above one.
+fn bytes(n: usize) -> String {
+    let units = ["B", "KiB", "MiB", "GiB"];
+    let mut size = n as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < units.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format! {"{n}B"}
+    } else {
+        format! {"{size:.1}{}", units[unit]}
+    }
+}
+
+fn print_table(results: &[DayResult]) {
+    let mut header = vec![
+        "Day", "User", "Part 1", "Part 2", "Check", "Parse", "Time 1", "Time 2",
+    ];
+    // Only builds with the `memory` feature measure it.
+    let memory = results.iter().any(|r| r.parse_memory.is_some());
+    if memory {
+        header.extend(["Mem P", "Mem 1", "Mem 2"]);
+    }
+    header.push("Errors");
+    let mut rows = Vec::new();
+    for result in results {
+        let mut row = vec![format! {"{:02}", result.day}, result.source.user()];
+        let mut errors = Vec::new();
+        match &result.parse {
+            Ok(parse) => {
+                let parts = [1, 2].map(|n| result.parts.iter().find(|p| p.part == n));
+                for part in parts {
+                    row.push(match part.map(|p| &p.answer) {
+                        Some(Ok(answer)) => answer.clone(),
+                        Some(Err(_)) => "-".to_string(),
+                        None => String::new(),
+                    });
+                }
+                for part in &result.parts {
+                    if let Err(e) = &part.answer {
+                        errors.push(format! {"part {}: {e:#}", part.part});
+                    }
+                    if let Check::Fail { expected } = &part.check {
+                        errors.push(format! {"part {}: expected {expected}", part.part});
+                    }
+                }
+                row.push(
+                    parts
+                        .map(|p| match p.map(|p| &p.check) {
+                            Some(Check::Pass) => "ok",
+                            Some(Check::Fail { .. }) => "FAIL",
+                            Some(Check::Unknown) => "?",
+                            None => "-",
+                        })
+                        .join("/"),
+                );
+                row.push(format! {"{parse:.2?}"});
+                for part in parts {
+                    row.push(
+                        part.map(|p| format! {"{:.2?}", p.elapsed})
+                            .unwrap_or_default(),
+                    );
+                }
+                if memory {
+                    row.push(result.parse_memory.map(bytes).unwrap_or_default());
+                    for part in parts {
+                        row.push(part.and_then(|p| p.memory).map(bytes).unwrap_or_default());
+                    }
+                }
+            }
+            Err(e) => {
+                row.resize(header.len() - 1, "-".to_string());
+                errors.push(format! {"parse: {e:#}"});
+            }
+        }
+        row.push(errors.join("; "));
+        rows.push(row);
+    }
+
+    table::print(&header, &rows);
+}
+
+/// One line of machine-readable output: a single part of one day's run, or
+/// just the parse step if that failed.
+#[derive(Debug, Default, PartialEq)]
+struct Record {
+    day: usize,
+    user: String,
+    input: String,
+    part: Option<u8>,
+    answer: Option<String>,
+    check: Option<&'static str>,
+    expected: Option<String>,
+    parse: Option<Duration>,
+    duration: Option<Duration>,
+    parse_bytes: Option<usize>,
+    peak_bytes: Option<usize>,
+    error: Option<String>,
+}
+
+fn records(results: &[DayResult]) -> Vec<Record> {
+    let mut records = Vec::new();
+    for result in results {
+        let base = Record {
+            day: result.day,
+            user: result.source.user(),
+            input: result.source.to_string(),
+            ..Record::default()
+        };
+        let parse = match &result.parse {
+            Ok(parse) => *parse,
+            Err(e) => {
+                records.push(Record {
+                    error: Some(format! {"{e:#}"}),
+                    ..base
+                });
+                continue;
+            }
+        };
+        for part in &result.parts {
+            let (check, expected) = match &part.check {
+                Check::Pass => ("pass", None),
+                Check::Fail { expected } => ("fail", Some(expected.clone())),
+                Check::Unknown => ("unknown", None),
+            };
+            records.push(Record {
+                user: base.user.clone(),
+                input: base.input.clone(),
+                part: Some(part.part),
+                answer: part.answer.as_ref().ok().cloned(),
+                check: Some(check),
+                expected,
+                parse: Some(parse),
+                duration: Some(part.elapsed),
+                parse_bytes: result.parse_memory,
+                peak_bytes: part.memory,
+                error: part.answer.as_ref().err().map(|e| format! {"{e:#}"}),
+                ..base
+            });
+        }
+    }
+    records
+}
+
+impl Record {
+    /// Field names and values in output order; durations are in nanoseconds.
+    /// The byte counts are only known with the `memory` feature.
+    fn fields(&self) -> [(&'static str, Option<String>); 12] {
+        [
+            ("day", Some(self.day.to_string())),
+            ("user", Some(self.user.clone())),
+            ("input", Some(self.input.clone())),
+            ("part", self.part.map(|p| p.to_string())),
+            ("answer", self.answer.clone()),
+            ("check", self.check.map(String::from)),
+            ("expected", self.expected.clone()),
+            ("parse_ns", self.parse.map(|d| d.as_nanos().to_string())),
+            (
+                "duration_ns",
+                self.duration.map(|d| d.as_nanos().to_string()),
+            ),
+            ("parse_bytes", self.parse_bytes.map(|b| b.to_string())),
+            ("peak_bytes", self.peak_bytes.map(|b| b.to_string())),
+            ("error", self.error.clone()),
+        ]
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format! {"\\u{:04x}", c as u32}),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json(records: &[Record]) -> String {
+    let numeric = [
+        "day",
+        "part",
+        "parse_ns",
+        "duration_ns",
+        "parse_bytes",
+        "peak_bytes",
+    ];
+    let objects = records
+        .iter()
+        .map(|record| {
+            let fields = record
+                .fields()
+                .into_iter()
+                .map(|(name, value)| {
+                    let value = match value {
+                        None => "null".to_string(),
+                        Some(v) if numeric.contains(&name) => v,
+                        Some(v) => json_string(&v),
+                    };
+                    format! {"\"{name}\": {value}"}
+                })
+                .collect::<Vec<_>>();
+            format! {"  {{{}}}", fields.join(", ")}
+        })
+        .collect::<Vec<_>>();
+    if objects.is_empty() {
+        return "[]\n".to_string();
+    }
+    format! {"[\n{}\n]\n", objects.join(",\n")}
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format! {"\"{}\"", s.replace('"', "\"\"")}
+    } else {
+        s.to_string()
+    }
+}
+
+fn csv(records: &[Record]) -> String {
+    let header = Record::default().fields().map(|(name, _)| name).join(",");
+    let mut out = format! {"{header}\n"};
+    for record in records {
+        let row = record
+            .fields()
+            .map(|(_, value)| csv_field(value.as_deref().unwrap_or_default()))
+            .join(",");
+        out.push_str(&row);
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record() -> Record {
+        Record {
+            day: 9,
+            user: "joshua".to_string(),
+            input: "puzzles/day09/joshua.input".to_string(),
+            part: Some(2),
+            check: Some("unknown"),
+            parse: Some(Duration::from_micros(41)),
+            duration: Some(Duration::from_nanos(93870)),
+            peak_bytes: Some(2048),
+            error: Some("Not Implemented, \"yet\"".to_string()),
+            ..Record::default()
+        }
+    }
+
+    #[test]
+    fn it_writes_json() {
+        assert_eq! {
+            json(&[record()]),
+            concat! {
+                "[\n",
+                r#"  {"day": 9, "user": "joshua", "input": "puzzles/day09/joshua.input", "#,
+                r#""part": 2, "answer": null, "check": "unknown", "expected": null, "#,
+                r#""parse_ns": 41000, "duration_ns": 93870, "#,
+                r#""parse_bytes": null, "peak_bytes": 2048, "#,
+                r#""error": "Not Implemented, \"yet\""}"#,
+                "\n]\n"
+            }
+        };
+        assert_eq! {json(&[]), "[]\n"};
+    }
+
+    #[test]
+    fn it_writes_csv() {
+        assert_eq! {
+            csv(&[record()]),
+            concat! {
+                "day,user,input,part,answer,check,expected,parse_ns,duration_ns,",
+                "parse_bytes,peak_bytes,error\n",
+                r#"9,joshua,puzzles/day09/joshua.input,2,,unknown,,41000,93870,,2048,"#,
+                r#""Not Implemented, ""yet""""#,
+                "\n"
+            }
+        };
+    }
+
+    #[test]
+    fn it_formats_bytes() {
+        assert_eq! {bytes(0), "0B"};
+        assert_eq! {bytes(1023), "1023B"};
+        assert_eq! {bytes(1536), "1.5KiB"};
+        assert_eq! {bytes(3 << 30), "3.0GiB"};
+    }
+}
@@ -0,0 +1,551 @@
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use solver::Solver;
+
+use crate::answers::{Check, Expected};
+use crate::cpu;
+use crate::memory;
+use crate::params::Params;
+use crate::pool;
+use crate::registry::Constructor;
+
+pub type Input = Box<dyn Iterator<Item = String>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Part {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    Both,
+}
+
+impl Part {
+    pub fn numbers(self) -> &'static [u8] {
+        match self {
+            Part::One => &[1],
+            Part::Two => &[2],
+            Part::Both => &[1, 2],
+        }
+    }
+
+    /// Narrows `self` to the parts `expected` has answers for, as an example
+    /// often only goes with one part. With no answers at all, nothing is
+    /// known about which parts apply, so `self` is kept.
+    pub fn restrict(self, expected: &Expected) -> Option<Part> {
+        let known = |part| expected.get(part).is_some();
+        match (self, known(1), known(2)) {
+            (_, false, false) | (Part::Both, true, true) => Some(self),
+            (Part::One | Part::Both, true, _) => Some(Part::One),
+            (Part::Two | Part::Both, _, true) => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+/// Parses `input` with `create`, rejecting any parameter it didn't ask for.
+pub fn construct(
+    create: Constructor,
+    input: Input,
+    params: &Params,
+) -> anyhow::Result<Box<dyn Solver + Send + Sync>> {
+    let solver = create(input, params)?;
+    params.check_used()?;
+    Ok(solver)
+}
+
+pub fn solve(solver: &dyn Solver, part: u8) -> anyhow::Result<String> {
+    match part {
+        1 => solver.part_one(),
+        2 => solver.part_two(),
+        _ => anyhow::bail! {"there is no part {part}"},
+    }
+}
+
+pub struct PartResult {
+    pub part: u8,
+    pub answer: anyhow::Result<String>,
+    pub elapsed: Duration,
+    /// Peak heap use in bytes, with the `memory` feature.
+    pub memory: Option<usize>,
+    pub check: Check,
+}
+
+pub struct DayResult {
+    pub day: usize,
+    pub source: Source,
+    pub parse: anyhow::Result<Duration>,
+    pub parse_memory: Option<usize>,
+    pub parts: Vec<PartResult>,
+    /// Whether `check` found any known answers, so that an input that
+    /// doesn't parse counts as failing them.
+    pub expects_answers: bool,
+}
+
+impl DayResult {
+    pub fn check(&mut self, expected: &Expected) {
+        self.expects_answers = [1, 2].iter().any(|&part| expected.get(part).is_some());
+        for part in &mut self.parts {
+            part.check = expected.check(part.part, &part.answer);
+        }
+    }
+
+    /// Parts that didn't match their known answers, plus one for an input
+    /// with known answers that didn't parse at all.
+    pub fn failures(&self) -> usize {
+        let parts = self
+            .parts
+            .iter()
+            .filter(|p| matches!(p.check, Check::Fail { .. }))
+            .count();
+        parts + usize::from(self.parse.is_err() && self.expects_answers)
+    }
+}
+
+pub fn puzzle_dir(day: usize) -> PathBuf {
+    PathBuf::from(format! {"puzzles/day{day:02}"})
+}
+
+/// Every personal `<user>.input` in the day's puzzle directory, sorted by
+/// user. The `example*.input` files belong to the tests and are skipped.
+pub fn user_inputs(day: usize) -> anyhow::Result<Vec<Source>> {
+    inputs(day, |stem| !stem.starts_with("example"))
+}
+
+/// The day's `example.input`, `example1.input`, `example2.input`, ..., whose
+/// answers live in the matching `.output` files just like personal inputs.
+pub fn example_inputs(day: usize) -> anyhow::Result<Vec<Source>> {
+    inputs(day, |stem| stem.starts_with("example"))
+}
+
+fn inputs(day: usize, keep: impl Fn(&str) -> bool) -> anyhow::Result<Vec<Source>> {
+    let dir = puzzle_dir(day);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut inputs = Vec::new();
+    for entry in
+        std::fs::read_dir(&dir).with_context(|| format! {"could not list {}", dir.display()})?
+    {
+        let path = entry?.path();
+        let is_input = path.extension().is_some_and(|e| e == "input");
+        let kept = path.file_stem().and_then(|s| s.to_str()).is_some_and(&keep);
+        if is_input && kept {
+            inputs.push(path);
+        }
+    }
+    // Shortest first, so that `example10` comes after `example9`.
+    inputs.sort_by_key(|path| (path.as_os_str().len(), path.clone()));
+    Ok(inputs.into_iter().map(Source::File).collect())
+}
+
+pub fn read_lines(path: &Path) -> anyhow::Result<Vec<String>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format! {"could not read {}", path.display()})?;
+    Ok(contents.lines().map(String::from).collect())
+}
+
+/// Where a single run gets its puzzle input from.
+#[derive(Debug, Clone)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+    Text(String),
+}
+
+impl Source {
+    /// `-` means stdin, as is traditional.
+    pub fn from_arg(input: &str) -> Self {
+        match input {
+            "-" => Source::Stdin,
+            path => Source::File(PathBuf::from(path)),
+        }
+    }
+
+    pub fn lines(&self) -> anyhow::Result<Vec<String>> {
+        let contents = match self {
+            Source::File(path) => return read_lines(path),
+            Source::Stdin => {
+                std::io::read_to_string(std::io::stdin()).context("could not read stdin")?
+            }
+            Source::Text(text) => text.clone(),
+        };
+        Ok(contents.lines().map(String::from).collect())
+    }
+
+    /// Whose input this is, going by the `<user>.input` naming.
+    pub fn user(&self) -> String {
+        match self {
+            Source::File(path) => path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            Source::Stdin => "stdin".to_string(),
+            Source::Text(_) => "text".to_string(),
+        }
+    }
+
+    /// The known answers only live next to input files.
+    pub fn output(&self) -> Option<PathBuf> {
+        match self {
+            Source::File(path) => Some(path.with_extension("output")),
+            Source::Stdin | Source::Text(_) => None,
+        }
+    }
+
+    /// The `.params` file next to an input, with `overrides` on top.
+    pub fn params(&self, overrides: &Params) -> anyhow::Result<Params> {
+        let params = match self {
+            Source::File(path) => Params::load(&path.with_extension("params"))?,
+            Source::Stdin | Source::Text(_) => Params::default(),
+        };
+        Ok(params.merge(overrides))
+    }
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::File(path) => write! {f, "{}", path.display()},
+            Source::Stdin => write! {f, "-"},
+            Source::Text(_) => write! {f, "<text>"},
+        }
+    }
+}
+
+/// Runs `f`, turning a panic into an error so that one broken day can't take
+/// down a whole `--all` run.
+pub fn guarded<T>(f: impl FnOnce() -> anyhow::Result<T>) -> anyhow::Result<T> {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result,
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            anyhow::bail! {"panicked: {message}"}
+        }
+    }
+}
+
+pub struct Measured<T> {
+    pub result: anyhow::Result<T>,
+    pub elapsed: Duration,
+    pub cpu: Option<Duration>,
+    pub memory: Option<usize>,
+}
+
+fn timed<T>(f: impl FnOnce() -> anyhow::Result<T>) -> Measured<T> {
+    let start = Instant::now();
+    let ((result, cpu), memory) = memory::peak(|| cpu::time(|| guarded(f)));
+    Measured {
+        result,
+        elapsed: start.elapsed(),
+        cpu,
+        memory,
+    }
+}
+
+/// How many threads that `limited` stopped waiting for are still running.
+static RUNAWAYS: AtomicUsize = AtomicUsize::new(0);
+
+pub fn runaways() -> usize {
+    RUNAWAYS.load(Ordering::SeqCst)
+}
+
+/// Like `timed`, but stops waiting for `f` once `timeout` has passed. A
+/// thread can't be stopped from outside, so one that overruns carries on in
+/// the background and its result is dropped; `runaways` counts those, as
+/// they compete with later work for the CPU.
+pub fn limited<T: Send + 'static>(
+    timeout: Option<Duration>,
+    f: impl FnOnce() -> anyhow::Result<T> + Send + 'static,
+) -> Measured<T> {
+    let Some(timeout) = timeout else {
+        return timed(f);
+    };
+    let (sender, receiver) = mpsc::channel();
+    // Set by whichever of the thread finishing and the wait timing out comes
+    // first, so the other knows whether the thread was abandoned.
+    let settled = Arc::new(AtomicBool::new(false));
+    let finished = Arc::clone(&settled);
+    std::thread::spawn(move || {
+        let _ = sender.send(timed(f));
+        if finished.swap(true, Ordering::SeqCst) {
+            RUNAWAYS.fetch_sub(1, Ordering::SeqCst);
+        }
+    });
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => {
+            RUNAWAYS.fetch_add(1, Ordering::SeqCst);
+            if settled.swap(true, Ordering::SeqCst) {
+                // It finished just as time ran out, so isn't running after all.
+                RUNAWAYS.fetch_sub(1, Ordering::SeqCst);
+                if let Ok(result) = receiver.recv() {
+                    return result;
+                }
+            }
+            Measured {
+                result: Err(anyhow::anyhow! {"timed out after {timeout:.2?}"}),
+                elapsed: timeout,
+                cpu: None,
+                memory: None,
+            }
+        }
+        Err(RecvTimeoutError::Disconnected) => Measured {
+            result: Err(anyhow::anyhow! {"the worker thread died"}),
+            elapsed: Duration::ZERO,
+            cpu: None,
+            memory: None,
+        },
+    }
+}
+
+/// Runs a single input, one part after the other.
+pub fn run(job: Job, timeout: Option<Duration>) -> DayResult {
+    let (mut results, _) = run_all(vec![job], 1, timeout);
+    results.pop().expect("one result per job")
+}
+
+/// One input of one day, for `run_all`.
+pub struct Job {
+    pub day: usize,
+    pub part: Part,
+    pub source: Source,
+    pub params: Params,
+    pub create: Constructor,
+}
+
+/// One thread per CPU.
+pub fn default_threads() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+pub struct Timing {
+    pub wall: Duration,
+    /// The CPU time of every parse and part added up, leaving out any that
+    /// timed out. `None` where CPU time can't be measured.
+    pub summed_cpu: Option<Duration>,
+}
+
+/// Runs every job on up to `threads` threads: first parsing every input, then
+/// solving all the parts, so both parts of a day can run at once. A parse or
+/// part that takes longer than `timeout` is reported as an error. The results
+/// come back in job order.
+pub fn run_all(
+    jobs: Vec<Job>,
+    threads: usize,
+    timeout: Option<Duration>,
+) -> (Vec<DayResult>, Timing) {
+    let start = Instant::now();
+    let parsed = pool::map(&jobs, threads, |job| match job.source.lines() {
+        Ok(lines) => {
+            let (create, params, source) = (job.create, job.params.clone(), job.source.clone());
+            limited(timeout, move || {
+                construct(create, Box::new(lines.into_iter()), &params)
+                    .map(Arc::from)
+                    .with_context(|| format! {"could not parse {source}"})
+            })
+        }
+        Err(e) => Measured {
+            result: Err(e),
+            elapsed: Duration::ZERO,
+            cpu: None,
+            memory: None,
+        },
+    });
+    let tasks = jobs
+        .iter()
+        .zip(&parsed)
+        .enumerate()
+        .filter(|(_, (_, parse))| parse.result.is_ok())
+        .flat_map(|(i, (job, _))| job.part.numbers().iter().map(move |&part| (i, part)))
+        .collect::<Vec<_>>();
+    let solved = pool::map(&tasks, threads, |&(i, part)| {
+        let solver = parsed[i]
+            .result
+            .as_ref()
+            .expect("only parsed inputs are solved");
+        let solver: Arc<dyn Solver + Send + Sync> = Arc::clone(solver);
+        limited(timeout, move || solve(solver.as_ref(), part))
+    });
+    let wall = start.elapsed();
+
+    let mut summed_cpu = None;
+    let mut add = |cpu: Option<Duration>| {
+        if let Some(cpu) = cpu {
+            summed_cpu = Some(summed_cpu.unwrap_or_default() + cpu);
+        }
+    };
+    let mut solved = tasks.into_iter().zip(solved).peekable();
+    let results = jobs
+        .into_iter()
+        .zip(parsed)
+        .enumerate()
+        .map(|(i, (job, parse))| {
+            add(parse.cpu);
+            let mut parts = Vec::new();
+            while let Some(((_, part), solve)) = solved.next_if(|((j, _), _)| *j == i) {
+                add(solve.cpu);
+                parts.push(PartResult {
+                    part,
+                    answer: solve.result,
+                    elapsed: solve.elapsed,
+                    memory: solve.memory,
+                    check: Check::Unknown,
+                });
+            }
+            DayResult {
+                day: job.day,
+                source: job.source,
+                parse: parse.result.map(|_| parse.elapsed),
+                parse_memory: parse.memory,
+                parts,
+                expects_answers: false,
+            }
+        })
+        .collect();
+    (results, Timing { wall, summed_cpu })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn it_matches_every_recorded_answer() -> anyhow::Result<()> {
+        let mut jobs = Vec::new();
+        let mut outputs = Vec::new();
+        for entry in registry::puzzles() {
+            let sources = [user_inputs(entry.day)?, example_inputs(entry.day)?].concat();
+            for source in sources {
+                let expected = Expected::load(&source.output().unwrap())?;
+                let Some(part) = Part::Both.restrict(&expected) else {
+                    continue;
+                };
+                jobs.push(Job {
+                    day: entry.day,
+                    part,
+                    params: source.params(&Params::default())?,
+                    source,
+                    create: entry.try_create,
+                });
+                outputs.push(expected);
+            }
+        }
+
+        let threads = default_threads();
+        let mut failures = Vec::new();
+        for (mut result, expected) in run_all(jobs, threads, None).0.into_iter().zip(outputs) {
+            let name = format! {"day {:02} {}", result.day, result.source.user()};
+            if let Err(e) = &result.parse {
+                failures.push(format! {"{name}: {e:#}"});
+            }
+            result.check(&expected);
+            for part in &result.parts {
+                if let Check::Fail { expected } = &part.check {
+                    failures.push(format! {
+                        "{name} part {}: expected {expected}, got {:?}",
+                        part.part,
+                        part.answer.as_ref().map_err(|e| e.to_string())
+                    });
+                }
+            }
+        }
+        assert! {failures.is_empty(), "{}", failures.join("\n")};
+        Ok(())
+    }
+
+    #[test]
+    fn it_only_runs_parts_with_known_answers() {
+        let expected = Expected::parse;
+        assert_eq! {Part::Both.restrict(&expected("")), Some(Part::Both)};
+        assert_eq! {Part::Both.restrict(&expected("3\n")), Some(Part::One)};
+        assert_eq! {Part::Both.restrict(&expected("\n6\n")), Some(Part::Two)};
+        assert_eq! {Part::Both.restrict(&expected("3\n6\n")), Some(Part::Both)};
+        assert_eq! {Part::Two.restrict(&expected("3\n6\n")), Some(Part::Two)};
+        assert_eq! {Part::One.restrict(&expected("\n6\n")), None};
+    }
+
+    struct Slow;
+
+    impl solver::SolverToAny for Slow {
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    impl Solver for Slow {
+        fn part_one(&self) -> anyhow::Result<String> {
+            Ok("quick".to_string())
+        }
+
+        fn part_two(&self) -> anyhow::Result<String> {
+            std::thread::sleep(Duration::from_secs(1));
+            Ok("slow".to_string())
+        }
+    }
+
+    #[test]
+    fn it_gives_up_on_slow_parts() {
+        let job = |day| Job {
+            day,
+            part: Part::Both,
+            source: Source::Text(String::new()),
+            params: Params::default(),
+            create: |_, _| Ok(Box::new(Slow)),
+        };
+        let timeout = Duration::from_millis(100);
+        let (results, timing) = run_all(vec![job(1), job(2)], 2, Some(timeout));
+        assert! {timing.wall < Duration::from_millis(900)};
+        for result in results {
+            assert_eq! {result.parts[0].answer.as_ref().unwrap(), "quick"};
+            let error = result.parts[1].answer.as_ref().unwrap_err().to_string();
+            assert! {error.contains("timed out"), "{error}"};
+        }
+
+        // Later work still starts while the abandoned parts carry on.
+        assert! {runaways() >= 2};
+        let mut quick = job(3);
+        quick.part = Part::One;
+        assert! {run(quick, Some(timeout)).parts[0].answer.is_ok()};
+        let start = Instant::now();
+        while runaways() > 0 {
+            assert! {start.elapsed() < Duration::from_secs(5), "the parts never finished"};
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn it_says_where_parsing_failed() -> anyhow::Result<()> {
+        let entry = registry::find(3)?;
+        let job = Job {
+            day: 3,
+            part: Part::Both,
+            source: Source::Text("987\n8x1\n".into()),
+            params: Params::default(),
+            create: entry.try_create,
+        };
+        let mut result = run(job, None);
+        assert_eq! {
+            format! {"{:#}", result.parse.as_ref().unwrap_err()},
+            "could not parse <text>: line 2, column 2: expected a digit, found \"x1\""
+        };
+
+        // Failing to parse only fails answers that are known.
+        result.check(&Expected::default());
+        assert_eq! {result.failures(), 0};
+        result.check(&Expected::parse("357\n"));
+        assert_eq! {result.failures(), 1};
+        Ok(())
+    }
+}
@@ -0,0 +1,158 @@
+use std::path::Path;
+
+use anyhow::Context;
+
+const TEMPLATE: &str = "src/day00.rs";
+const MAIN: &str = "src/main.rs";
+const REGISTRY: &str = "src/registry.rs";
+
+/// Creates `src/dayNN.rs` from the day 0 template, registers it in
+/// `main.rs` and `registry.rs`, and creates `puzzles/dayNN/example.input`.
+/// All paths are relative to `root`, the crate directory.
+pub fn new_day(root: &Path, day: usize, title: &str) -> anyhow::Result<()> {
+    if !(1..=25).contains(&day) {
+        anyhow::bail! {"day {day} is not between 1 and 25"};
+    }
+    let module = format! {"day{day:02}"};
+    let source = root.join(format! {"src/{module}.rs"});
+    if source.exists() {
+        anyhow::bail! {"{} already exists", source.display()};
+    }
+
+    let template = read(&root.join(TEMPLATE))?;
+    let registry = splice(
+        &read(&root.join(REGISTRY))?,
+        "// BEGIN_SOLVER_LIST",
+        "// END_SOLVER_LIST",
+        format! {"    Entry::new({day}, {title:?}, crate::{module}::Day{day:02}::try_create),"},
+        |line| {
+            line.trim()
+                .strip_prefix("Entry::new(")?
+                .split(',')
+                .next()?
+                .parse()
+                .ok()
+        },
+    )?;
+    let main = splice(
+        &read(&root.join(MAIN))?,
+        "// BEGIN_MOD_LIST",
+        "// END_MOD_LIST",
+        format! {"mod {module};"},
+        |line| {
+            line.trim()
+                .strip_prefix("mod day")?
+                .strip_suffix(';')?
+                .parse()
+                .ok()
+        },
+    )?;
+
+    // Everything has been checked; only now start writing.
+    write(
+        &source,
+        &template
+            .replace("day00", &module)
+            .replace("Day00", &format! {"Day{day:02}"}),
+    )?;
+    write(&root.join(REGISTRY), &registry)?;
+    write(&root.join(MAIN), &main)?;
+    let puzzles = root.join(format! {"puzzles/{module}"});
+    std::fs::create_dir_all(&puzzles)
+        .with_context(|| format! {"could not create {}", puzzles.display()})?;
+    let example = puzzles.join("example.input");
+    if !example.exists() {
+        write(&example, "")?;
+    }
+    Ok(())
+}
+
+fn read(path: &Path) -> anyhow::Result<String> {
+    std::fs::read_to_string(path).with_context(|| format! {"could not read {}", path.display()})
+}
+
+fn write(path: &Path, contents: &str) -> anyhow::Result<()> {
+    std::fs::write(path, contents).with_context(|| format! {"could not write {}", path.display()})
+}
+
+/// Adds `line` to the lines between the `begin` and `end` markers, keeping
+/// them sorted by the day number `key` extracts from each.
+fn splice(
+    contents: &str,
+    begin: &str,
+    end: &str,
+    line: String,
+    key: impl Fn(&str) -> Option<usize>,
+) -> anyhow::Result<String> {
+    let lines = contents.lines().collect::<Vec<_>>();
+    let (Some(start), Some(stop)) = (
+        lines.iter().position(|l| l.trim() == begin),
+        lines.iter().position(|l| l.trim() == end),
+    ) else {
+        anyhow::bail! {"could not find the {begin} and {end} markers"};
+    };
+    let new_key = key(&line);
+    let mut list = lines[start + 1..stop].to_vec();
+    if list.iter().any(|l| key(l).is_some() && key(l) == new_key) {
+        anyhow::bail! {"{} is already registered", line.trim()};
+    }
+    list.push(&line);
+    list.sort_by_key(|l| key(l));
+
+    let mut spliced = lines[..=start].to_vec();
+    spliced.extend(list);
+    spliced.extend(&lines[stop..]);
+    Ok(spliced.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_keeps_lists_in_day_order() -> anyhow::Result<()> {
+        let contents = "a\n// BEGIN\nmod day02;\nmod day10;\n// END\nb\n";
+        let key = |l: &str| l.strip_prefix("mod day")?.strip_suffix(';')?.parse().ok();
+        let spliced = splice(contents, "// BEGIN", "// END", "mod day09;".into(), key)?;
+        assert_eq! {spliced, "a\n// BEGIN\nmod day02;\nmod day09;\nmod day10;\n// END\nb\n"};
+        assert! {splice(contents, "// BEGIN", "// END", "mod day02;".into(), key).is_err()};
+        assert! {splice(contents, "// START", "// END", "mod day09;".into(), key).is_err()};
+        Ok(())
+    }
+
+    #[test]
+    fn it_creates_a_new_day() -> anyhow::Result<()> {
+        let root =
+            std::env::temp_dir().join(format! {"aoc2025_rs-scaffold-{}", std::process::id()});
+        std::fs::create_dir_all(root.join("src"))?;
+        for file in [TEMPLATE, MAIN, REGISTRY] {
+            std::fs::copy(file, root.join(file))?;
+        }
+
+        new_day(&root, 25, "Finale")?;
+        let source = read(&root.join("src/day25.rs"))?;
+        assert! {source.contains("pub struct Day25 {}")};
+        assert! {source.contains("Day25, \"day25\", bench: example;")};
+        assert! {read(&root.join(MAIN))?.contains("mod day25;\n// END_MOD_LIST")};
+        assert! {read(&root.join(REGISTRY))?.contains(
+            "    Entry::new(25, \"Finale\", crate::day25::Day25::try_create),\n    // END_SOLVER_LIST"
+        )};
+        // The tests include every input in the table, so each has to exist.
+        let table = source
+            .lines()
+            .skip_while(|line| !line.contains("Day25, \"day25\", bench: example;"))
+            .skip(1)
+            .take_while(|line| line.trim() != "}")
+            .collect::<Vec<_>>();
+        assert! {!table.is_empty(), "{source}"};
+        for row in table {
+            let input = row.trim().split([':', '(']).next().unwrap_or_default();
+            let path = root.join(format! {"puzzles/day25/{input}.input"});
+            assert! {path.exists(), "{} is missing", path.display()};
+        }
+        assert! {new_day(&root, 25, "Finale").is_err()};
+
+        std::fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+/// Prints `rows` under `header` as a plain-text table, padding every column to
+/// its widest cell.
+pub fn print(header: &[&str], rows: &[Vec<String>]) {
+    let mut widths = header.iter().map(|h| h.len()).collect::<Vec<_>>();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let line = |cells: Vec<&str>| {
+        let padded = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format! {"{cell:<width$}"})
+            .collect::<Vec<_>>();
+        println! {"{}", padded.join(" | ").trim_end()};
+    };
+    line(header.to_vec());
+    println! {"{}", widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>().join("-+-")};
+    for row in rows {
+        line(row.iter().map(String::as_str).collect());
+    }
+}
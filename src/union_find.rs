@@ -0,0 +1,110 @@
+/// Disjoint sets over the ids `0..len`, each starting out on its own. Finding
+/// a set compresses the path to its root, and merging hangs the smaller set
+/// under the larger, so both are close to constant time. Ids outside `0..len`
+/// panic, like indexing a `Vec`.
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    /// Only meaningful for roots: how many ids are in the root's set.
+    size: Vec<usize>,
+    count: usize,
+}
+
+impl UnionFind {
+    pub fn new(len: usize) -> Self {
+        UnionFind {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            count: len,
+        }
+    }
+
+    /// The root of the set holding `id`, which stands for the whole set.
+    pub fn find(&mut self, id: usize) -> usize {
+        let mut root = id;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut id = id;
+        while self.parent[id] != root {
+            id = std::mem::replace(&mut self.parent[id], root);
+        }
+        root
+    }
+
+    /// Joins the sets holding `a` and `b`, returning false if they were
+    /// already one set.
+    pub fn merge(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            (a, b) = (b, a);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.count -= 1;
+        true
+    }
+
+    /// How many separate sets there are.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Every set's ids, in order of each set's smallest id.
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut index = vec![None; self.parent.len()];
+        let mut components = Vec::<Vec<usize>>::with_capacity(self.count);
+        for id in 0..self.parent.len() {
+            let root = self.find(id);
+            let i = *index[root].get_or_insert(components.len());
+            if i == components.len() {
+                components.push(Vec::with_capacity(self.size[root]));
+            }
+            components[i].push(id);
+        }
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_merges_sets() {
+        let mut sets = UnionFind::new(6);
+        assert_eq! {sets.count(), 6};
+        assert! {sets.merge(0, 1)};
+        assert! {sets.merge(2, 3)};
+        assert! {sets.merge(1, 3)};
+        assert! {!sets.merge(0, 2)};
+        assert_eq! {sets.find(0), sets.find(3)};
+        assert_ne! {sets.find(0), sets.find(4)};
+        assert_eq! {sets.count(), 3};
+        assert_eq! {sets.components(), [vec![0, 1, 2, 3], vec![4], vec![5]]};
+
+        assert! {sets.merge(5, 4)};
+        assert_eq! {sets.count(), 2};
+        assert_eq! {sets.components(), [vec![0, 1, 2, 3], vec![4, 5]]};
+
+        // The smaller set goes under the larger one's root.
+        let root = sets.find(0);
+        assert! {sets.merge(5, 0)};
+        assert_eq! {sets.find(5), root};
+        assert_eq! {(sets.count(), sets.components().len()), (1, 1)};
+    }
+
+    #[test]
+    fn it_compresses_paths() {
+        let mut sets = UnionFind::new(5);
+        // A chain 4 -> 3 -> 2 -> 1 -> 0, as merging without sizes could build.
+        sets.parent = vec![0, 0, 1, 2, 3];
+        sets.size[0] = 5;
+        sets.count = 1;
+        assert_eq! {sets.find(4), 0};
+        assert_eq! {sets.parent, [0; 5]};
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::Context;
+
+use crate::report::{self, Format};
+use crate::runner::{self, DayResult};
+
+/// Each file's modification time and size, to notice edits by polling.
+type Snapshot = BTreeMap<PathBuf, (SystemTime, u64)>;
+
+fn snapshot(dir: &Path) -> anyhow::Result<Snapshot> {
+    let mut files = Snapshot::new();
+    if !dir.exists() {
+        return Ok(files);
+    }
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format! {"could not list {}", dir.display()})?
+    {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_file() {
+            files.insert(entry.path(), (metadata.modified()?, metadata.len()));
+        }
+    }
+    Ok(files)
+}
+
+/// The files that were added, changed or removed between two snapshots.
+fn changed(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut paths = before
+        .keys()
+        .chain(after.keys())
+        .filter(|path| before.get(*path) != after.get(*path))
+        .cloned()
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+/// Each part's outcome by input and part number, errors included, so that a
+/// part starting or stopping failing shows up as a change too.
+type Answers = BTreeMap<(String, u8), String>;
+
+fn answers(results: &[DayResult]) -> Answers {
+    let mut answers = Answers::new();
+    for result in results {
+        let user = result.source.user();
+        if let Err(e) = &result.parse {
+            answers.insert((user, 0), format! {"parse error: {e:#}"});
+            continue;
+        }
+        for part in &result.parts {
+            let answer = match &part.answer {
+                Ok(answer) => answer.clone(),
+                Err(e) => format! {"error: {e:#}"},
+            };
+            answers.insert((user.clone(), part.part), answer);
+        }
+    }
+    answers
+}
+
+/// One line per answer that differs from the last run.
+fn diff(before: &Answers, after: &Answers) -> Vec<String> {
+    let mut keys = before.keys().chain(after.keys()).collect::<Vec<_>>();
+    keys.sort();
+    keys.dedup();
+    keys.into_iter()
+        .filter_map(|key| {
+            let (user, part) = key;
+            let name = match part {
+                0 => user.clone(),
+                part => format! {"{user} part {part}"},
+            };
+            match (before.get(key), after.get(key)) {
+                (Some(old), Some(new)) if old != new => Some(format! {"{name}: {old} -> {new}"}),
+                (None, Some(new)) => Some(format! {"{name}: new, {new}"}),
+                (Some(old), None) => Some(format! {"{name}: gone, was {old}"}),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Calls `run` whenever a file in `dir` changes, checking every `interval`,
+/// and prints its results along with how the answers moved since last time.
+/// Only returns if `dir` can't be read.
+pub fn watch(
+    dir: &Path,
+    interval: Duration,
+    mut run: impl FnMut() -> anyhow::Result<Vec<DayResult>>,
+) -> anyhow::Result<()> {
+    let mut files = Snapshot::new();
+    let mut last = None;
+    loop {
+        let now = snapshot(dir)?;
+        let paths = changed(&files, &now);
+        files = now;
+        if paths.is_empty() {
+            std::thread::sleep(interval);
+            continue;
+        }
+
+        if last.is_some() {
+            for path in paths {
+                println! {"changed: {}", path.display()};
+            }
+        }
+        let running = runner::runaways();
+        if running > 0 {
+            println! {"{running} parse(s) or part(s) that timed out last time are still running"};
+        }
+        match run() {
+            Ok(results) => {
+                report::print(Format::Table, &results);
+                let answers = answers(&results);
+                if let Some(last) = &last {
+                    let lines = diff(last, &answers);
+                    if lines.is_empty() {
+                        println! {"no answers changed"};
+                    }
+                    for line in lines {
+                        println! {"{line}"};
+                    }
+                }
+                last = Some(answers);
+            }
+            Err(e) => eprintln! {"error: {e:#}"},
+        }
+        println! {"watching {} for changes", dir.display()};
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_notices_changed_files() {
+        let at = |secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+        let before = Snapshot::from([
+            ("a.input".into(), (at(1), 10)),
+            ("a.output".into(), (at(1), 4)),
+            ("b.input".into(), (at(1), 10)),
+        ]);
+        let after = Snapshot::from([
+            ("a.input".into(), (at(2), 10)),
+            ("a.output".into(), (at(1), 4)),
+            ("c.input".into(), (at(2), 10)),
+        ]);
+        assert_eq! {
+            changed(&before, &after),
+            ["a.input", "b.input", "c.input"].map(PathBuf::from)
+        };
+        assert! {changed(&after, &after).is_empty()};
+    }
+
+    #[test]
+    fn it_diffs_answers() {
+        let answers = |pairs: &[(&str, u8, &str)]| {
+            pairs
+                .iter()
+                .map(|(user, part, answer)| ((user.to_string(), *part), answer.to_string()))
+                .collect::<Answers>()
+        };
+        let before = answers(&[
+            ("example", 1, "3"),
+            ("example", 2, "6"),
+            ("joshua", 1, "962"),
+        ]);
+        let after = answers(&[
+            ("example", 1, "3"),
+            ("example", 2, "7"),
+            ("example2", 2, "6"),
+        ]);
+        assert_eq! {
+            diff(&before, &after),
+            [
+                "example part 2: 6 -> 7",
+                "example2 part 2: new, 6",
+                "joshua part 1: gone, was 962",
+            ]
+        };
+    }
+}